@@ -8,7 +8,10 @@ use cw721::{
     AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, NftInfoResponse,
     NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
 };
-use cw721_soulbound::{ExecuteMsg, Extension, InstantiateMsg, MinterResponse, QueryMsg};
+use cw721_soulbound::{
+    DelegationsResponse, ExecuteMsg, Extension, InstantiateMsg, MinterResponse, MintersResponse,
+    NftInfosResponse, QueryMsg,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -30,14 +33,21 @@ fn main() {
     );
     export_schema(&schema_for!(ApprovalResponse), &out_dir);
     export_schema(&schema_for!(ApprovalsResponse), &out_dir);
+    export_schema(&schema_for!(DelegationsResponse), &out_dir);
     export_schema(&schema_for!(OperatorsResponse), &out_dir);
     export_schema(&schema_for!(ContractInfoResponse), &out_dir);
     export_schema(&schema_for!(MinterResponse), &out_dir);
+    export_schema(&schema_for!(MintersResponse), &out_dir);
     export_schema_with_title(
         &schema_for!(NftInfoResponse<Extension>),
         &out_dir,
         "NftInfoResponse",
     );
+    export_schema_with_title(
+        &schema_for!(NftInfosResponse<Extension>),
+        &out_dir,
+        "NftInfosResponse",
+    );
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
     export_schema(&schema_for!(OwnerOfResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
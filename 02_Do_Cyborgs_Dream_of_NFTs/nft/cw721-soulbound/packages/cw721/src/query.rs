@@ -77,6 +77,31 @@ pub struct OwnerOfResponse {
     pub owner: String,
     /// If set this address is approved to transfer/send the token as well
     pub approvals: Vec<Approval>,
+    /// Whether the token has been revoked or has expired
+    #[serde(default)]
+    pub validity: TokenValidity,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenValidity {
+    Valid {},
+    /// Revoked by the minter. The token is kept, but can no longer be relied upon
+    Revoked { reason: String },
+    /// The token's expiration has passed
+    Expired { expires: Expiration },
+}
+
+impl Default for TokenValidity {
+    fn default() -> Self {
+        TokenValidity::Valid {}
+    }
+}
+
+impl TokenValidity {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, TokenValidity::Valid {})
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -104,7 +129,20 @@ pub struct OperatorsResponse {
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct NumTokensResponse {
+    /// Tokens currently in existence, including revoked tokens
     pub count: u64,
+    /// Tokens ever minted
+    #[serde(default)]
+    pub minted: u64,
+    /// Tokens ever burned
+    #[serde(default)]
+    pub burned: u64,
+    /// Tokens revoked, which are kept rather than burned
+    #[serde(default)]
+    pub revoked: u64,
+    /// Tokens in existence that haven't been revoked
+    #[serde(default)]
+    pub active: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
@@ -121,6 +159,9 @@ pub struct NftInfoResponse<T> {
     pub token_uri: Option<String>,
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: T,
+    /// Whether the token has been revoked or has expired
+    #[serde(default)]
+    pub validity: TokenValidity,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
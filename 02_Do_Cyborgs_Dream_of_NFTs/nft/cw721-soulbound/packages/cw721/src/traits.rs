@@ -85,7 +85,7 @@ where
 
     fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse>;
 
-    fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>>;
+    fn nft_info(&self, deps: Deps, env: Env, token_id: String) -> StdResult<NftInfoResponse<T>>;
 
     fn owner_of(
         &self,
@@ -1,14 +1,19 @@
 #![cfg(test)]
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-use cosmwasm_std::{DepsMut, Empty, Response,};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order,
+    Response, StdError, StdResult,
+};
 
 use cw721::{
-    Approval, ApprovalResponse, ContractInfoResponse, Cw721Query, Expiration,
-    NftInfoResponse, OwnerOfResponse,
+    Approval, ApprovalResponse, ContractInfoResponse, CustomMsg, Cw721Query, Expiration,
+    NftInfoResponse, NumTokensResponse, OwnerOfResponse, TokenValidity, TokensResponse,
 };
 
+use crate::state::TokenInfo;
 use crate::{
-    ContractError, Cw721Contract, ExecuteMsg, InstantiateMsg, MintMsg,
+    ContractError, Cw721Contract, DelegationInfo, DelegationScope, ExecuteMsg, ExtensionHandler,
+    InstantiateMsg, MintMsg, MinterInfo, NftInfosResponse, QueryMsg,
 };
 
 use schemars::JsonSchema;
@@ -34,6 +39,7 @@ fn setup_contract(deps: DepsMut<'_>) -> Cw721Contract<'static, Extension, Empty,
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         minter: String::from(MINTER),
+        unique_owners: false,
     };
     let info = mock_info("creator", &[]);
     let res = contract.instantiate(deps, mock_env(), info, msg).unwrap();
@@ -50,6 +56,7 @@ fn proper_instantiation() {
         name: CONTRACT_NAME.to_string(),
         symbol: SYMBOL.to_string(),
         minter: String::from(MINTER),
+        unique_owners: false,
     };
     let info = mock_info("creator", &[]);
 
@@ -92,6 +99,8 @@ fn minting() {
         owner: String::from("medusa"),
         token_uri: Some(token_uri.clone()),
         extension: None,
+        expires: None,
+        notify: None,
     });
 
     // random cannot mint
@@ -113,16 +122,17 @@ fn minting() {
 
     // unknown nft returns error
     let _ = contract
-        .nft_info(deps.as_ref(), "unknown".to_string())
+        .nft_info(deps.as_ref(), mock_env(), "unknown".to_string())
         .unwrap_err();
 
     // this nft info is correct
-    let info = contract.nft_info(deps.as_ref(), token_id.clone()).unwrap();
+    let info = contract.nft_info(deps.as_ref(), mock_env(), token_id.clone()).unwrap();
     assert_eq!(
         info,
         NftInfoResponse::<Extension> {
             token_uri: Some(token_uri),
             extension: None,
+            validity: TokenValidity::Valid {},
         }
     );
 
@@ -135,6 +145,7 @@ fn minting() {
         OwnerOfResponse {
             owner: String::from("medusa"),
             approvals: vec![],
+            validity: TokenValidity::Valid {},
         }
     );
 
@@ -144,6 +155,8 @@ fn minting() {
         owner: String::from("hercules"),
         token_uri: None,
         extension: None,
+        expires: None,
+        notify: None,
     });
 
     let allowed = mock_info(MINTER, &[]);
@@ -158,6 +171,58 @@ fn minting() {
     assert_eq!(vec![token_id], tokens.tokens);
 }
 
+#[test]
+fn minting_with_unique_owners() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty, Empty, Empty>::default();
+    let msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        unique_owners: true,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg)
+        .unwrap();
+
+    let mint_msg = |token_id: &str| {
+        ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: String::from("arthur"),
+            token_uri: None,
+            extension: None,
+            expires: None,
+            notify: None,
+        })
+    };
+    let minter = mock_info(MINTER, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg("excalibur"))
+        .unwrap();
+
+    // arthur can only have one
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg("clarent"))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::OwnerHasToken {
+            owner: String::from("arthur")
+        }
+    );
+
+    // until he gives it up
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: "excalibur".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("arthur", &[]), burn_msg)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg("clarent"))
+        .unwrap();
+}
+
 #[test]
 fn burning() {
     let mut deps = mock_dependencies();
@@ -171,6 +236,8 @@ fn burning() {
         owner: MINTER.to_string(),
         token_uri: Some(token_uri),
         extension: None,
+        expires: None,
+        notify: None,
     });
 
     let burn_msg = ExecuteMsg::Burn { token_id };
@@ -199,7 +266,7 @@ fn burning() {
 
     // trying to get nft returns error
     let _ = contract
-        .nft_info(deps.as_ref(), "petrify".to_string())
+        .nft_info(deps.as_ref(), mock_env(), "petrify".to_string())
         .unwrap_err();
 
     // list the token_ids
@@ -207,6 +274,660 @@ fn burning() {
     assert!(tokens.tokens.is_empty());
 }
 
+#[test]
+fn counting_tokens() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let minter = mock_info(MINTER, &[]);
+    for token_id in ["grail", "sword", "stone"] {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: MINTER.to_string(),
+            token_uri: None,
+            extension: None,
+            expires: None,
+            notify: None,
+        });
+        contract
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+    let revoke_msg = ExecuteMsg::RevokeToken {
+        token_id: "grail".to_string(),
+        reason: "Lost".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), revoke_msg)
+        .unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: "sword".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), burn_msg)
+        .unwrap();
+
+    let expected = NumTokensResponse {
+        count: 2,
+        minted: 3,
+        burned: 1,
+        revoked: 1,
+        active: 1,
+    };
+    assert_eq!(contract.num_tokens(deps.as_ref()).unwrap(), expected);
+
+    // a count that's out of sync is an error, rather than a panic
+    contract.token_count.save(&mut deps.storage, &0).unwrap();
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: "stone".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), burn_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::InconsistentTokenCount {});
+
+    // only the admin can reconcile the counts
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), ExecuteMsg::ReconcileTokens {})
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::ReconcileTokens {},
+        )
+        .unwrap();
+    assert_eq!(contract.num_tokens(deps.as_ref()).unwrap(), expected);
+
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, burn_msg)
+        .unwrap();
+    let count = contract.num_tokens(deps.as_ref()).unwrap();
+    assert_eq!((count.count, count.burned, count.active), (1, 2, 0));
+}
+
+#[test]
+fn minting_with_multiple_minters() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let mint_msg = |token_id: &str| {
+        ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+            expires: None,
+            notify: None,
+        })
+    };
+
+    // only the admin can add minters
+    let add_msg = ExecuteMsg::AddMinter {
+        minter: String::from("morgana"),
+        quota: Some(1),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), add_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), add_msg)
+        .unwrap();
+
+    // new minters can mint up to their quota
+    let morgana = mock_info("morgana", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), morgana.clone(), mint_msg("curse"))
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), morgana.clone(), mint_msg("hex"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::QuotaExceeded { quota: 1 });
+
+    // raising the quota keeps count of what's been minted
+    let add_msg = ExecuteMsg::AddMinter {
+        minter: String::from("morgana"),
+        quota: Some(2),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), add_msg)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), mock_env(), morgana.clone(), mint_msg("hex"))
+        .unwrap();
+
+    // the original minter has no quota
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg("petrify"))
+        .unwrap();
+
+    let minters = contract.minters(deps.as_ref(), None, None).unwrap();
+    assert_eq!(
+        minters.minters,
+        vec![
+            MinterInfo {
+                minter: String::from(MINTER),
+                quota: None,
+                minted: 1,
+            },
+            MinterInfo {
+                minter: String::from("morgana"),
+                quota: Some(2),
+                minted: 2,
+            },
+        ]
+    );
+    let minters = contract
+        .minters(deps.as_ref(), Some(String::from(MINTER)), None)
+        .unwrap();
+    assert_eq!(minters.minters.len(), 1);
+
+    // removed minters can't mint
+    let remove_msg = ExecuteMsg::RemoveMinter {
+        minter: String::from("morgana"),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), remove_msg.clone())
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), remove_msg)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MinterNotFound {
+            minter: String::from("morgana")
+        }
+    );
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), morgana, mint_msg("charm"))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the minter named at instantiation is always reported as the minter,
+    // even once minters that sort before it are added, or it's removed
+    let add_msg = ExecuteMsg::AddMinter {
+        minter: String::from("arthur"),
+        quota: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), add_msg)
+        .unwrap();
+    let res = contract.minter(deps.as_ref()).unwrap();
+    assert_eq!(MINTER, res.minter);
+    let remove_msg = ExecuteMsg::RemoveMinter {
+        minter: String::from(MINTER),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), remove_msg)
+        .unwrap();
+    let res = contract.minter(deps.as_ref()).unwrap();
+    assert_eq!(MINTER, res.minter);
+}
+
+#[test]
+fn updating_metadata() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: Some(Metadata {
+            name: Some("Medusa".to_string()),
+            image: None,
+            description: None,
+        }),
+        expires: None,
+        notify: None,
+    });
+    let minter = mock_info(MINTER, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+        .unwrap();
+
+    let extension = Some(Metadata {
+        name: Some("Medusa".to_string()),
+        image: Some("ipfs://gorgon".to_string()),
+        description: Some("Don't look".to_string()),
+    });
+    let update_msg = ExecuteMsg::UpdateMetadata {
+        token_id: token_id.clone(),
+        extension: extension.clone(),
+    };
+
+    // not even the owner can update metadata
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("medusa", &[]), update_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // minter can
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), update_msg.clone())
+        .unwrap();
+    let info = contract
+        .nft_info(deps.as_ref(), mock_env(), token_id.clone())
+        .unwrap();
+    assert_eq!(info.extension, extension);
+
+    // revoked tokens are frozen
+    let revoke_msg = ExecuteMsg::RevokeToken {
+        token_id,
+        reason: "Petrified".to_string(),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), minter.clone(), revoke_msg)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), minter, update_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Revoked {});
+}
+
+#[test]
+fn altering_tokens_of_other_minters() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let add_msg = ExecuteMsg::AddMinter {
+        minter: String::from("morgana"),
+        quota: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), add_msg)
+        .unwrap();
+
+    let token_id = "aegis".to_string();
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+        expires: None,
+        notify: None,
+    });
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap();
+    let token = contract.tokens.load(&deps.storage, &token_id).unwrap();
+    assert_eq!(token.issuer, Some(Addr::unchecked(MINTER)));
+
+    let update_msg = ExecuteMsg::UpdateMetadata {
+        token_id: token_id.clone(),
+        extension: Some(Metadata {
+            name: Some("Gorgoneion".to_string()),
+            image: None,
+            description: None,
+        }),
+    };
+    let revoke_msg = ExecuteMsg::RevokeToken {
+        token_id,
+        reason: "Stolen".to_string(),
+    };
+
+    // another minter can neither update nor revoke a token it didn't issue
+    let morgana = mock_info("morgana", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), morgana.clone(), update_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), morgana, revoke_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // nor can the issuer once it's no longer a minter
+    let remove_msg = ExecuteMsg::RemoveMinter {
+        minter: String::from(MINTER),
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), remove_msg)
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), update_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the admin always can
+    let admin = mock_info("creator", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), admin.clone(), update_msg)
+        .unwrap();
+    contract
+        .execute(deps.as_mut(), mock_env(), admin, revoke_msg)
+        .unwrap();
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum RenameMsg {
+    Rename { token_id: String, name: String },
+}
+
+impl CustomMsg for RenameMsg {}
+
+// Lets a token's owner rename it, something the base contract can't do
+struct RenameExtension;
+
+impl ExtensionHandler<Extension, Empty, RenameMsg, Empty> for RenameExtension {
+    fn execute_extension(
+        &self,
+        contract: &Cw721Contract<Extension, Empty, RenameMsg, Empty>,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        msg: RenameMsg,
+    ) -> Result<Response, ContractError> {
+        let RenameMsg::Rename { token_id, name } = msg;
+        let mut token = contract.tokens.load(deps.storage, &token_id)?;
+        if token.owner != info.sender {
+            return Err(ContractError::Unauthorized {});
+        }
+        if let Some(metadata) = token.extension.as_mut() {
+            metadata.name = Some(name);
+        }
+        contract.tokens.save(deps.storage, &token_id, &token)?;
+        Ok(Response::new().add_attribute("action", "rename"))
+    }
+}
+
+#[test]
+fn executing_extensions() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty, RenameMsg, Empty>::default();
+    let init_msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        unique_owners: false,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), init_msg)
+        .unwrap();
+
+    let token_id = "shapeshift".to_string();
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: String::from("proteus"),
+        token_uri: None,
+        extension: Some(Metadata {
+            name: Some("Proteus".to_string()),
+            image: None,
+            description: None,
+        }),
+        expires: None,
+        notify: None,
+    });
+    contract
+        .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+        .unwrap();
+
+    let rename_msg = ExecuteMsg::Extension {
+        msg: RenameMsg::Rename {
+            token_id: token_id.clone(),
+            name: "Seal".to_string(),
+        },
+    };
+
+    // plain execute ignores extension messages
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), mock_info("proteus", &[]), rename_msg.clone())
+        .unwrap();
+    assert_eq!(res, Response::default());
+
+    // the handler enforces its own rules
+    let err = contract
+        .execute_with_extension(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("menelaus", &[]),
+            rename_msg.clone(),
+            &RenameExtension,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    contract
+        .execute_with_extension(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("proteus", &[]),
+            rename_msg,
+            &RenameExtension,
+        )
+        .unwrap();
+    let info = contract
+        .nft_info(deps.as_ref(), mock_env(), token_id)
+        .unwrap();
+    assert_eq!(info.extension.unwrap().name, Some("Seal".to_string()));
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum NameQueryMsg {
+    TokensByName { name: String },
+}
+
+impl CustomMsg for NameQueryMsg {}
+
+fn token_name_idx(d: &TokenInfo<Extension>) -> String {
+    d.extension
+        .as_ref()
+        .and_then(|metadata| metadata.name.clone())
+        .unwrap_or_default()
+}
+
+// Looks tokens up by name, through an index on the extension
+struct NameLookup;
+
+impl ExtensionHandler<Extension, Empty, Empty, NameQueryMsg> for NameLookup {
+    fn execute_extension(
+        &self,
+        _contract: &Cw721Contract<Extension, Empty, Empty, NameQueryMsg>,
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: Empty,
+    ) -> Result<Response, ContractError> {
+        Ok(Response::default())
+    }
+
+    fn query_extension(
+        &self,
+        contract: &Cw721Contract<Extension, Empty, Empty, NameQueryMsg>,
+        deps: Deps,
+        _env: Env,
+        msg: NameQueryMsg,
+    ) -> StdResult<Binary> {
+        let NameQueryMsg::TokensByName { name } = msg;
+        let tokens = contract
+            .tokens
+            .idx
+            .extension("tokens__name")
+            .unwrap()
+            .prefix(name)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        to_binary(&TokensResponse { tokens })
+    }
+}
+
+#[test]
+fn querying_extension_indexes() {
+    let mut deps = mock_dependencies();
+    let contract = Cw721Contract::<Extension, Empty, Empty, NameQueryMsg>::with_indexes(vec![(
+        "tokens__name",
+        token_name_idx,
+    )]);
+    let init_msg = InstantiateMsg {
+        name: CONTRACT_NAME.to_string(),
+        symbol: SYMBOL.to_string(),
+        minter: String::from(MINTER),
+        unique_owners: false,
+    };
+    contract
+        .instantiate(deps.as_mut(), mock_env(), mock_info("creator", &[]), init_msg)
+        .unwrap();
+
+    let tokens = [("grail", "Holy Grail"), ("sword", "Excalibur"), ("stone", "Holy Grail")];
+    for (token_id, name) in tokens {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: String::from("arthur"),
+            token_uri: None,
+            extension: Some(Metadata {
+                name: Some(name.to_string()),
+                image: None,
+                description: None,
+            }),
+            expires: None,
+            notify: None,
+        });
+        contract
+            .execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg)
+            .unwrap();
+    }
+
+    let by_name = |name: &str| QueryMsg::Extension {
+        msg: NameQueryMsg::TokensByName { name: name.to_string() },
+    };
+
+    // plain query rejects extension messages rather than answering with nothing
+    let err = contract
+        .query(deps.as_ref(), mock_env(), by_name("Holy Grail"))
+        .unwrap_err();
+    assert_eq!(err, StdError::generic_err("extension queries not supported"));
+
+    let res = contract
+        .query_with_extension(deps.as_ref(), mock_env(), by_name("Holy Grail"), &NameLookup)
+        .unwrap();
+    let found: TokensResponse = from_binary(&res).unwrap();
+    assert_eq!(found.tokens, vec!["grail".to_string(), "stone".to_string()]);
+
+    // burned tokens drop out of extension indexes
+    contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("arthur", &[]),
+            ExecuteMsg::Burn { token_id: "grail".to_string() },
+        )
+        .unwrap();
+    let res = contract
+        .query_with_extension(deps.as_ref(), mock_env(), by_name("Holy Grail"), &NameLookup)
+        .unwrap();
+    let found: TokensResponse = from_binary(&res).unwrap();
+    assert_eq!(found.tokens, vec!["stone".to_string()]);
+}
+
+#[test]
+fn revoking_and_expiring() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "petrify".to_string();
+    let expiring_id = "enchant".to_string();
+    let minter = mock_info(MINTER, &[]);
+
+    // tokens can't be minted already expired
+    let env = mock_env();
+    let expired_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: expiring_id.clone(),
+        owner: String::from("medusa"),
+        token_uri: None,
+        extension: None,
+        expires: Some(Expiration::AtHeight(env.block.height)),
+        notify: None,
+    });
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), minter.clone(), expired_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::MintExpired {});
+
+    let mint_msgs = vec![
+        ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+            expires: None,
+            notify: None,
+        }),
+        ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: expiring_id.clone(),
+            owner: String::from("medusa"),
+            token_uri: None,
+            extension: None,
+            expires: Some(Expiration::AtHeight(env.block.height + 10)),
+            notify: None,
+        }),
+    ];
+    for msg in mint_msgs {
+        contract
+            .execute(deps.as_mut(), env.clone(), minter.clone(), msg)
+            .unwrap();
+    }
+
+    // only the minter can revoke
+    let revoke_msg = ExecuteMsg::RevokeToken {
+        token_id: token_id.clone(),
+        reason: "Turned a hero to stone".to_string(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), mock_info("medusa", &[]), revoke_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    contract
+        .execute(deps.as_mut(), env.clone(), minter.clone(), revoke_msg.clone())
+        .unwrap();
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), minter, revoke_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Revoked {});
+
+    // revoked tokens are kept, but marked invalid
+    let revoked = TokenValidity::Revoked {
+        reason: "Turned a hero to stone".to_string(),
+    };
+    let info = contract
+        .nft_info(deps.as_ref(), env.clone(), token_id.clone())
+        .unwrap();
+    assert_eq!(info.validity, revoked);
+    let owner = contract
+        .owner_of(deps.as_ref(), env.clone(), token_id.clone(), false)
+        .unwrap();
+    assert_eq!(owner.validity, revoked);
+
+    // and can't be burned by their owner
+    let burn_msg = ExecuteMsg::Burn { token_id };
+    let err = contract
+        .execute(deps.as_mut(), env.clone(), mock_info("medusa", &[]), burn_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Revoked {});
+
+    // expiring tokens are valid until they expire
+    let info = contract
+        .nft_info(deps.as_ref(), env.clone(), expiring_id.clone())
+        .unwrap();
+    assert!(info.validity.is_valid());
+    let mut later = env;
+    later.block.height += 10;
+    let owner = contract
+        .owner_of(deps.as_ref(), later, expiring_id, false)
+        .unwrap();
+    assert_eq!(
+        owner.validity,
+        TokenValidity::Expired {
+            expires: Expiration::AtHeight(12355),
+        }
+    );
+}
+
 #[test]
 fn approving_revoking() {
     let mut deps = mock_dependencies();
@@ -221,6 +942,8 @@ fn approving_revoking() {
         owner: String::from("demeter"),
         token_uri: Some(token_uri),
         extension: None,
+        expires: None,
+        notify: None,
     });
 
     let minter = mock_info(MINTER, &[]);
@@ -302,7 +1025,7 @@ fn approving_revoking() {
 
     // trying to get nft now returns an error
     let _ = contract
-        .nft_info(deps.as_ref(), token_id.clone())
+        .nft_info(deps.as_ref(), mock_env(), token_id.clone())
         .unwrap_err();
 }
 
@@ -323,6 +1046,8 @@ fn approving_all_revoking_all() {
         owner: String::from("demeter"),
         token_uri: Some(token_uri1),
         extension: None,
+        expires: None,
+        notify: None,
     });
 
     let minter = mock_info(MINTER, &[]);
@@ -335,6 +1060,8 @@ fn approving_all_revoking_all() {
         owner: String::from("demeter"),
         token_uri: Some(token_uri2),
         extension: None,
+        expires: None,
+        notify: None,
     });
 
     contract
@@ -379,10 +1106,211 @@ fn approving_all_revoking_all() {
 
     // trying to get nft now returns an error
     let _ = contract
-        .nft_info(deps.as_ref(), token_id1.clone())
+        .nft_info(deps.as_ref(), mock_env(), token_id1.clone())
         .unwrap_err();
 }
 
+#[test]
+fn delegating_scopes() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+
+    let token_id = "grow".to_string();
+    let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+        token_id: token_id.clone(),
+        owner: String::from("demeter"),
+        token_uri: None,
+        extension: None,
+        expires: None,
+        notify: None,
+    });
+    let minter = mock_info(MINTER, &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), minter, mint_msg)
+        .unwrap();
+
+    // owner hands out recovery until a given height, and image updates for good
+    let owner = mock_info("demeter", &[]);
+    let expires = Expiration::AtHeight(mock_env().block.height + 10);
+    let delegate_msg = ExecuteMsg::Delegate {
+        token_id: token_id.clone(),
+        delegate: String::from("hermes"),
+        scope: DelegationScope::Recover,
+        expires: Some(expires),
+    };
+    let res = contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), delegate_msg)
+        .unwrap();
+    assert_eq!(
+        res,
+        Response::new()
+            .add_attribute("action", "delegate")
+            .add_attribute("sender", "demeter")
+            .add_attribute("delegate", "hermes")
+            .add_attribute("token_id", token_id.clone())
+            .add_attribute("scope", "recover")
+    );
+    let delegate_msg = ExecuteMsg::Delegate {
+        token_id: token_id.clone(),
+        delegate: String::from("hermes"),
+        scope: DelegationScope::UpdateImage,
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), delegate_msg)
+        .unwrap();
+
+    // a delegate can't act outside its scopes
+    let hermes = mock_info("hermes", &[]);
+    let burn_msg = ExecuteMsg::Burn {
+        token_id: token_id.clone(),
+    };
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), hermes.clone(), burn_msg.clone())
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let token = contract.tokens.load(&deps.storage, &token_id).unwrap();
+    contract
+        .check_delegated(
+            deps.as_ref(),
+            &mock_env(),
+            &hermes,
+            &token,
+            DelegationScope::Recover,
+        )
+        .unwrap();
+
+    // nor can it hand its scopes on
+    let delegate_msg = ExecuteMsg::Delegate {
+        token_id: token_id.clone(),
+        delegate: String::from("iris"),
+        scope: DelegationScope::Recover,
+        expires: None,
+    };
+    let err = contract
+        .execute(
+            deps.as_mut(),
+            mock_env(),
+            hermes.clone(),
+            delegate_msg.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // operators can only delegate burning
+    let approve_all_msg = ExecuteMsg::ApproveAll {
+        operator: String::from("zeus"),
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner.clone(), approve_all_msg)
+        .unwrap();
+    let zeus = mock_info("zeus", &[]);
+    let err = contract
+        .execute(deps.as_mut(), mock_env(), zeus.clone(), delegate_msg)
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let delegate_msg = ExecuteMsg::Delegate {
+        token_id: token_id.clone(),
+        delegate: String::from("hades"),
+        scope: DelegationScope::Burn,
+        expires: None,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), zeus, delegate_msg)
+        .unwrap();
+
+    // every scope is listed, but legacy approvals only show burning
+    let delegations = contract
+        .delegations(deps.as_ref(), mock_env(), token_id.clone(), false)
+        .unwrap()
+        .delegations;
+    assert_eq!(
+        delegations,
+        vec![
+            DelegationInfo {
+                delegate: String::from("hermes"),
+                scope: DelegationScope::Recover,
+                expires,
+            },
+            DelegationInfo {
+                delegate: String::from("hermes"),
+                scope: DelegationScope::UpdateImage,
+                expires: Expiration::Never {},
+            },
+            DelegationInfo {
+                delegate: String::from("hades"),
+                scope: DelegationScope::Burn,
+                expires: Expiration::Never {},
+            },
+        ]
+    );
+    let approvals = contract
+        .approvals(deps.as_ref(), mock_env(), token_id.clone(), false)
+        .unwrap()
+        .approvals;
+    assert_eq!(
+        approvals,
+        vec![Approval {
+            spender: String::from("hades"),
+            expires: Expiration::Never {},
+        }]
+    );
+
+    // expired delegations are hidden, and no longer grant anything
+    let mut later = mock_env();
+    later.block.height += 20;
+    let delegations = contract
+        .delegations(deps.as_ref(), later.clone(), token_id.clone(), false)
+        .unwrap()
+        .delegations;
+    assert_eq!(delegations.len(), 2);
+    let delegations = contract
+        .delegations(deps.as_ref(), later.clone(), token_id.clone(), true)
+        .unwrap()
+        .delegations;
+    assert_eq!(delegations.len(), 3);
+    let err = contract
+        .check_delegated(
+            deps.as_ref(),
+            &later,
+            &hermes,
+            &token,
+            DelegationScope::Recover,
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // undelegating only removes the one scope
+    let undelegate_msg = ExecuteMsg::Undelegate {
+        token_id: token_id.clone(),
+        delegate: String::from("hermes"),
+        scope: DelegationScope::Recover,
+    };
+    contract
+        .execute(deps.as_mut(), mock_env(), owner, undelegate_msg)
+        .unwrap();
+    let delegations = contract
+        .delegations(deps.as_ref(), mock_env(), token_id.clone(), true)
+        .unwrap()
+        .delegations;
+    assert_eq!(delegations.len(), 2);
+    assert_eq!(delegations[0].scope, DelegationScope::UpdateImage);
+
+    // and the burn delegate can burn
+    let hades = mock_info("hades", &[]);
+    contract
+        .execute(deps.as_mut(), mock_env(), hades, burn_msg)
+        .unwrap();
+    let count = contract.num_tokens(deps.as_ref()).unwrap();
+    assert_eq!(0, count.count);
+
+    // approvals stored before delegations were scoped still burn
+    let legacy: crate::state::Approval =
+        cosmwasm_std::from_slice(br#"{"spender":"hades","expires":{"never":{}}}"#).unwrap();
+    assert_eq!(legacy.scope, DelegationScope::Burn);
+}
+
 #[test]
 fn query_tokens_by_owner() {
     let mut deps = mock_dependencies();
@@ -401,6 +1329,8 @@ fn query_tokens_by_owner() {
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        expires: None,
+        notify: None,
     });
     contract
         .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
@@ -411,6 +1341,8 @@ fn query_tokens_by_owner() {
         owner: ceres.clone(),
         token_uri: None,
         extension: None,
+        expires: None,
+        notify: None,
     });
     contract
         .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
@@ -421,6 +1353,8 @@ fn query_tokens_by_owner() {
         owner: demeter.clone(),
         token_uri: None,
         extension: None,
+        expires: None,
+        notify: None,
     });
     contract
         .execute(deps.as_mut(), mock_env(), minter, mint_msg)
@@ -459,3 +1393,91 @@ fn query_tokens_by_owner() {
         .unwrap();
     assert_eq!(&by_demeter[1..], &tokens.tokens[..]);
 }
+
+#[test]
+fn query_nft_infos() {
+    let mut deps = mock_dependencies();
+    let contract = setup_contract(deps.as_mut());
+    let minter = mock_info(MINTER, &[]);
+
+    let demeter = String::from("demeter");
+    let ceres = String::from("ceres");
+    let mints = [
+        (
+            "grow1",
+            &demeter,
+            "https://www.merriam-webster.com/dictionary/grow",
+        ),
+        (
+            "grow2",
+            &ceres,
+            "https://www.merriam-webster.com/dictionary/flourish",
+        ),
+        (
+            "sing",
+            &demeter,
+            "https://www.merriam-webster.com/dictionary/sing",
+        ),
+    ];
+    for (token_id, owner, token_uri) in mints {
+        let mint_msg = ExecuteMsg::Mint(MintMsg::<Extension> {
+            token_id: token_id.to_string(),
+            owner: owner.clone(),
+            token_uri: Some(token_uri.to_string()),
+            extension: None,
+            expires: None,
+            notify: None,
+        });
+        contract
+            .execute(deps.as_mut(), mock_env(), minter.clone(), mint_msg)
+            .unwrap();
+    }
+
+    let ids = |res: NftInfosResponse<Extension>| -> Vec<String> {
+        res.tokens.into_iter().map(|t| t.token_id).collect()
+    };
+
+    // all tokens come back with their info, in order
+    let res = contract
+        .all_nft_infos(deps.as_ref(), mock_env(), None, None)
+        .unwrap();
+    assert_eq!(res.tokens.len(), 3);
+    assert_eq!(res.tokens[1].token_id, "grow2");
+    assert_eq!(
+        res.tokens[1].info,
+        NftInfoResponse::<Extension> {
+            token_uri: Some(mints[1].2.to_string()),
+            extension: None,
+            validity: TokenValidity::Valid {},
+        }
+    );
+    // paginate
+    let res = contract
+        .all_nft_infos(deps.as_ref(), mock_env(), None, Some(2))
+        .unwrap();
+    assert_eq!(ids(res), vec!["grow1", "grow2"]);
+    let res = contract
+        .all_nft_infos(deps.as_ref(), mock_env(), Some("grow2".to_string()), None)
+        .unwrap();
+    assert_eq!(ids(res), vec!["sing"]);
+
+    // filtered by owner
+    let res = contract
+        .nft_infos_by_owner(deps.as_ref(), mock_env(), demeter.clone(), None, None)
+        .unwrap();
+    assert_eq!(ids(res), vec!["grow1", "sing"]);
+    let res = contract
+        .nft_infos_by_owner(deps.as_ref(), mock_env(), ceres, None, None)
+        .unwrap();
+    assert_eq!(ids(res), vec!["grow2"]);
+    let res = contract
+        .nft_infos_by_owner(
+            deps.as_ref(),
+            mock_env(),
+            demeter,
+            Some("grow1".to_string()),
+            Some(1),
+        )
+        .unwrap();
+    assert_eq!(ids(res), vec!["sing"]);
+}
@@ -15,6 +15,39 @@ pub enum ContractError {
     #[error("Cannot set approval that is already expired")]
     Expired {},
 
+    #[error("{owner} already owns a token")]
+    OwnerHasToken { owner: String },
+
+    #[error("Cannot mint a token that has already expired")]
+    MintExpired {},
+
+    #[error("Minter has already minted its quota of {quota}")]
+    QuotaExceeded { quota: u64 },
+
+    #[error("Not a minter: {minter}")]
+    MinterNotFound { minter: String },
+
+    #[error("token_id has been revoked")]
+    Revoked {},
+
+    #[error("Cannot change {field} once minted")]
+    ImmutableField { field: String },
+
+    #[error("Token count is out of sync with the stored tokens and must be reconciled")]
+    InconsistentTokenCount {},
+
+    #[error("A recovery threshold of {threshold} can't be met by {guardians} guardians")]
+    InvalidRecoveryThreshold { threshold: u32, guardians: u32 },
+
+    #[error("No recovery is pending")]
+    NoRecoveryPending {},
+
+    #[error("A recovery to {pending} is already pending")]
+    RecoveryMismatch { pending: String },
+
+    #[error("Recovery can't be completed until {unlocks_at}")]
+    RecoveryTimelocked { unlocks_at: String },
+
     #[error("Approval not found for: {spender}")]
     ApprovalNotFound { spender: String },
 }
@@ -1,14 +1,18 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    Addr, ContractInfoResponse as WasmContractInfoResponse, Deps, DepsMut, Env, MessageInfo,
+    Order, Response, StdResult, WasmQuery,
+};
 
 use cw2::set_contract_version;
-use cw721::{ContractInfoResponse, CustomMsg, Cw721Execute, Expiration};
+use cw721::{ContractInfoResponse, CustomMsg, Cw721Execute, Cw721ReceiveMsg, Expiration};
 
 use crate::error::ContractError;
+use crate::extension::{ExtensionHandler, IgnoreExtension};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
+use crate::state::{Approval, Cw721Contract, DelegationScope, MinterQuota, TokenInfo};
 
 // Version info for migration
 const CONTRACT_NAME: &str = "crates.io:cw721-soulbound";
@@ -25,18 +29,29 @@ where
         &self,
         deps: DepsMut,
         _env: Env,
-        _info: MessageInfo,
+        info: MessageInfo,
         msg: InstantiateMsg,
     ) -> StdResult<Response<C>> {
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-        let info = ContractInfoResponse {
+        let contract_info = ContractInfoResponse {
             name: msg.name,
             symbol: msg.symbol,
         };
-        self.contract_info.save(deps.storage, &info)?;
+        self.contract_info.save(deps.storage, &contract_info)?;
+        self.admin.save(deps.storage, &info.sender)?;
+        self.tokens
+            .idx
+            .unique_owner
+            .enabled
+            .save(deps.storage, &msg.unique_owners)?;
         let minter = deps.api.addr_validate(&msg.minter)?;
-        self.minter.save(deps.storage, &minter)?;
+        let quota = MinterQuota {
+            quota: None,
+            minted: 0,
+        };
+        self.minters.save(deps.storage, &minter, &quota)?;
+        self.initial_minter.save(deps.storage, &minter)?;
         Ok(Response::default())
     }
 
@@ -47,8 +62,27 @@ where
         info: MessageInfo,
         msg: ExecuteMsg<T, E>,
     ) -> Result<Response<C>, ContractError> {
+        self.execute_with_extension(deps, env, info, msg, &IgnoreExtension)
+    }
+
+    /// Like `execute`, but passes `ExecuteMsg::Extension` messages on to `handler`
+    pub fn execute_with_extension<H>(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg<T, E>,
+        handler: &H,
+    ) -> Result<Response<C>, ContractError>
+    where
+        H: ExtensionHandler<T, C, E, Q>,
+    {
         match msg {
             ExecuteMsg::Mint(msg) => self.mint(deps, env, info, msg),
+            ExecuteMsg::AddMinter { minter, quota } => {
+                self.add_minter(deps, env, info, minter, quota)
+            }
+            ExecuteMsg::RemoveMinter { minter } => self.remove_minter(deps, env, info, minter),
             ExecuteMsg::Approve {
                 spender,
                 token_id,
@@ -61,8 +95,27 @@ where
                 self.approve_all(deps, env, info, operator, expires)
             }
             ExecuteMsg::RevokeAll { operator } => self.revoke_all(deps, env, info, operator),
+            ExecuteMsg::Delegate {
+                token_id,
+                delegate,
+                scope,
+                expires,
+            } => self.delegate(deps, env, info, token_id, delegate, scope, expires),
+            ExecuteMsg::Undelegate {
+                token_id,
+                delegate,
+                scope,
+            } => self.undelegate(deps, env, info, token_id, delegate, scope),
             ExecuteMsg::Burn { token_id } => self.burn(deps, env, info, token_id),
-            ExecuteMsg::Extension { msg: _ } => Ok(Response::default()),
+            ExecuteMsg::UpdateMetadata {
+                token_id,
+                extension,
+            } => self.update_metadata(deps, env, info, token_id, extension),
+            ExecuteMsg::RevokeToken { token_id, reason } => {
+                self.revoke_token(deps, env, info, token_id, reason)
+            }
+            ExecuteMsg::ReconcileTokens {} => self.reconcile_tokens(deps, env, info),
+            ExecuteMsg::Extension { msg } => handler.execute_extension(self, deps, env, info, msg),
         }
     }
 }
@@ -78,23 +131,43 @@ where
     pub fn mint(
         &self,
         deps: DepsMut,
-        _env: Env,
+        env: Env,
         info: MessageInfo,
         msg: MintMsg<T>,
     ) -> Result<Response<C>, ContractError> {
-        let minter = self.minter.load(deps.storage)?;
+        let mut minter = self
+            .minters
+            .may_load(deps.storage, &info.sender)?
+            .ok_or(ContractError::Unauthorized {})?;
+
+        // each minter can only mint its quota
+        if let Some(quota) = minter.quota {
+            if minter.minted >= quota {
+                return Err(ContractError::QuotaExceeded { quota });
+            }
+        }
 
-        if info.sender != minter {
-            return Err(ContractError::Unauthorized {});
+        // reject expired tokens as invalid
+        if msg.expires.map(|e| e.is_expired(&env.block)).unwrap_or(false) {
+            return Err(ContractError::MintExpired {});
         }
 
         // create the token
+        let owner = deps.api.addr_validate(&msg.owner)?;
         let token = TokenInfo {
-            owner: deps.api.addr_validate(&msg.owner)?,
+            owner: owner.clone(),
             approvals: vec![],
             token_uri: msg.token_uri,
             extension: msg.extension,
+            revoked: None,
+            expires: msg.expires,
+            issuer: Some(info.sender.clone()),
         };
+
+        // the unique owner index would reject this anyway, but not as clearly
+        if self.tokens.idx.unique_owner.owns_token(deps.storage, &token.owner)? {
+            return Err(ContractError::OwnerHasToken { owner: msg.owner });
+        }
         self.tokens
             .update(deps.storage, &msg.token_id, |old| match old {
                 Some(_) => Err(ContractError::Claimed {}),
@@ -102,13 +175,198 @@ where
             })?;
 
         self.increment_tokens(deps.storage)?;
+        minter.minted += 1;
+        self.minters.save(deps.storage, &info.sender, &minter)?;
+
+        // let contract owners react to receiving their token
+        let mut res = Response::new();
+        if let Some(notify) = msg.notify {
+            if is_contract(deps.as_ref(), &owner) {
+                let callback = Cw721ReceiveMsg {
+                    sender: info.sender.to_string(),
+                    token_id: msg.token_id.clone(),
+                    msg: notify,
+                };
+                res = res.add_message(callback.into_cosmos_msg(owner)?);
+            }
+        }
 
-        Ok(Response::new()
+        Ok(res
             .add_attribute("action", "mint")
             .add_attribute("minter", info.sender)
             .add_attribute("owner", msg.owner)
             .add_attribute("token_id", msg.token_id))
     }
+
+    pub fn add_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        minter: String,
+        quota: Option<u64>,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        // keep count of what an existing minter has already minted
+        let minter_addr = deps.api.addr_validate(&minter)?;
+        let minted = self
+            .minters
+            .may_load(deps.storage, &minter_addr)?
+            .map(|m| m.minted)
+            .unwrap_or_default();
+        self.minters
+            .save(deps.storage, &minter_addr, &MinterQuota { quota, minted })?;
+
+        Ok(Response::new()
+            .add_attribute("action", "add_minter")
+            .add_attribute("minter", minter)
+            .add_attribute("quota", quota.map(|q| q.to_string()).unwrap_or_else(|| "none".to_string())))
+    }
+
+    pub fn remove_minter(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        minter: String,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let minter_addr = deps.api.addr_validate(&minter)?;
+        if !self.minters.has(deps.storage, &minter_addr) {
+            return Err(ContractError::MinterNotFound { minter });
+        }
+        self.minters.remove(deps.storage, &minter_addr);
+
+        Ok(Response::new()
+            .add_attribute("action", "remove_minter")
+            .add_attribute("minter", minter))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn delegate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        delegate: String,
+        scope: DelegationScope,
+        expires: Option<Expiration>,
+    ) -> Result<Response<C>, ContractError> {
+        self._update_approvals(
+            deps, &env, &info, &delegate, &token_id, scope, true, expires,
+        )?;
+
+        Ok(Response::new()
+            .add_attribute("action", "delegate")
+            .add_attribute("sender", info.sender)
+            .add_attribute("delegate", delegate)
+            .add_attribute("token_id", token_id)
+            .add_attribute("scope", scope.to_string()))
+    }
+
+    pub fn undelegate(
+        &self,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        token_id: String,
+        delegate: String,
+        scope: DelegationScope,
+    ) -> Result<Response<C>, ContractError> {
+        self._update_approvals(deps, &env, &info, &delegate, &token_id, scope, false, None)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "undelegate")
+            .add_attribute("sender", info.sender)
+            .add_attribute("delegate", delegate)
+            .add_attribute("token_id", token_id)
+            .add_attribute("scope", scope.to_string()))
+    }
+
+    pub fn update_metadata(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        extension: T,
+    ) -> Result<Response<C>, ContractError> {
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        self.check_issuer(deps.as_ref(), &info, &token)?;
+        if token.revoked.is_some() {
+            return Err(ContractError::Revoked {});
+        }
+        token.extension = extension;
+        self.tokens.save(deps.storage, &token_id, &token)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "update_metadata")
+            .add_attribute("minter", info.sender)
+            .add_attribute("token_id", token_id))
+    }
+
+    pub fn revoke_token(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+        token_id: String,
+        reason: String,
+    ) -> Result<Response<C>, ContractError> {
+        let mut token = self.tokens.load(deps.storage, &token_id)?;
+        self.check_issuer(deps.as_ref(), &info, &token)?;
+        if token.revoked.is_some() {
+            return Err(ContractError::Revoked {});
+        }
+        token.revoked = Some(reason.clone());
+        self.tokens.save(deps.storage, &token_id, &token)?;
+        self.increment_revoked(deps.storage)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "revoke_token")
+            .add_attribute("minter", info.sender)
+            .add_attribute("token_id", token_id)
+            .add_attribute("reason", reason))
+    }
+
+    pub fn reconcile_tokens(
+        &self,
+        deps: DepsMut,
+        _env: Env,
+        info: MessageInfo,
+    ) -> Result<Response<C>, ContractError> {
+        self.check_admin(deps.as_ref(), &info)?;
+
+        let (mut count, mut revoked) = (0u64, 0u64);
+        for item in self.tokens.range(deps.storage, None, None, Order::Ascending) {
+            let (_, token) = item?;
+            count += 1;
+            if token.revoked.is_some() {
+                revoked += 1;
+            }
+        }
+
+        // burns can't be recounted, so trust the burn count and
+        // take every token minted to be either burned or still here
+        let mut supply = self.token_supply(deps.storage)?;
+        supply.minted = supply
+            .burned
+            .checked_add(count)
+            .ok_or(ContractError::InconsistentTokenCount {})?;
+        supply.revoked = revoked;
+        self.token_count.save(deps.storage, &count)?;
+        self.token_supply.save(deps.storage, &supply)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "reconcile_tokens")
+            .add_attribute("count", count.to_string())
+            .add_attribute("minted", supply.minted.to_string())
+            .add_attribute("burned", supply.burned.to_string())
+            .add_attribute("revoked", supply.revoked.to_string()))
+    }
 }
 
 impl<'a, T, C, E, Q> Cw721Execute<T, C> for Cw721Contract<'a, T, C, E, Q>
@@ -129,7 +387,16 @@ where
         token_id: String,
         expires: Option<Expiration>,
     ) -> Result<Response<C>, ContractError> {
-        self._update_approvals(deps, &env, &info, &spender, &token_id, true, expires)?;
+        self._update_approvals(
+            deps,
+            &env,
+            &info,
+            &spender,
+            &token_id,
+            DelegationScope::Burn,
+            true,
+            expires,
+        )?;
 
         Ok(Response::new()
             .add_attribute("action", "approve")
@@ -146,7 +413,16 @@ where
         spender: String,
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
-        self._update_approvals(deps, &env, &info, &spender, &token_id, false, None)?;
+        self._update_approvals(
+            deps,
+            &env,
+            &info,
+            &spender,
+            &token_id,
+            DelegationScope::Burn,
+            false,
+            None,
+        )?;
 
         Ok(Response::new()
             .add_attribute("action", "revoke")
@@ -205,10 +481,15 @@ where
         token_id: String,
     ) -> Result<Response<C>, ContractError> {
         let token = self.tokens.load(deps.storage, &token_id)?;
-        self.check_can_send(deps.as_ref(), &env, &info, &token)?;
+        self.check_delegated(deps.as_ref(), &env, &info, &token, DelegationScope::Burn)?;
+
+        // revoked tokens are kept for audit
+        if token.revoked.is_some() {
+            return Err(ContractError::Revoked {});
+        }
 
-        self.tokens.remove(deps.storage, &token_id)?;
         self.decrement_tokens(deps.storage)?;
+        self.tokens.remove(deps.storage, &token_id)?;
 
         Ok(Response::new()
             .add_attribute("action", "burn")
@@ -233,17 +514,25 @@ where
         info: &MessageInfo,
         spender: &str,
         token_id: &str,
+        scope: DelegationScope,
         // if add == false, remove. if add == true, remove then set with this expiration
         add: bool,
         expires: Option<Expiration>,
     ) -> Result<TokenInfo<T>, ContractError> {
         let mut token = self.tokens.load(deps.storage, token_id)?;
-        // ensure we have permissions
-        self.check_can_approve(deps.as_ref(), env, info, &token)?;
+        // ensure we have permissions. Operators can only burn,
+        // so that's all they can hand on
+        match scope {
+            DelegationScope::Burn => self.check_can_approve(deps.as_ref(), env, info, &token)?,
+            _ if token.owner != info.sender => return Err(ContractError::Unauthorized {}),
+            _ => {}
+        }
 
-        // update the approval list (remove any for the same spender before adding)
+        // update the approval list (remove any for the same spender and scope before adding)
         let spender_addr = deps.api.addr_validate(spender)?;
-        token.approvals.retain(|apr| apr.spender != spender_addr);
+        token
+            .approvals
+            .retain(|apr| apr.spender != spender_addr || apr.scope != scope);
 
         // only difference between approve and revoke
         if add {
@@ -255,6 +544,7 @@ where
             let approval = Approval {
                 spender: spender_addr,
                 expires,
+                scope,
             };
             token.approvals.push(approval);
         }
@@ -264,6 +554,39 @@ where
         Ok(token)
     }
 
+    /// returns an error unless the sender can add and remove minters
+    pub fn check_admin(&self, deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+        let admin = self.admin.load(deps.storage)?;
+        if info.sender != admin {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    /// returns an error unless the sender is one of the contract minters
+    pub fn check_minter(&self, deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+        if !self.minters.has(deps.storage, &info.sender) {
+            return Err(ContractError::Unauthorized {});
+        }
+        Ok(())
+    }
+
+    /// returns an error unless the sender is the admin, or the minter that
+    /// issued the token and is still a minter. Other minters can't alter it
+    pub fn check_issuer(
+        &self,
+        deps: Deps,
+        info: &MessageInfo,
+        token: &TokenInfo<T>,
+    ) -> Result<(), ContractError> {
+        if token.issuer.as_ref() == Some(&info.sender)
+            && self.minters.has(deps.storage, &info.sender)
+        {
+            return Ok(());
+        }
+        self.check_admin(deps, info)
+    }
+
     /// returns true if the sender can execute approve or reject on the contract
     pub fn check_can_approve(
         &self,
@@ -292,29 +615,33 @@ where
         }
     }
 
-    /// returns true if the sender can transfer ownership of the token
-    pub fn check_can_send(
+    /// returns an error unless the sender owns the token, or it's been delegated to them for `scope`
+    pub fn check_delegated(
         &self,
         deps: Deps,
         env: &Env,
         info: &MessageInfo,
         token: &TokenInfo<T>,
+        scope: DelegationScope,
     ) -> Result<(), ContractError> {
-        // owner can send
+        // owner can do anything
         if token.owner == info.sender {
             return Ok(());
         }
 
-        // any non-expired token approval can send
+        // any non-expired delegation for this scope
         if token
             .approvals
             .iter()
-            .any(|apr| apr.spender == info.sender && !apr.is_expired(&env.block))
+            .any(|apr| apr.grants(&env.block, &info.sender, scope))
         {
             return Ok(());
         }
 
-        // operator can send
+        // operators can only burn
+        if scope != DelegationScope::Burn {
+            return Err(ContractError::Unauthorized {});
+        }
         let op = self
             .operators
             .may_load(deps.storage, (&token.owner, &info.sender))?;
@@ -330,3 +657,13 @@ where
         }
     }
 }
+
+// Only contracts have contract info, so the query fails for anyone else
+fn is_contract(deps: Deps, addr: &Addr) -> bool {
+    let query = WasmQuery::ContractInfo {
+        contract_addr: addr.to_string(),
+    };
+    deps.querier
+        .query::<WasmContractInfoResponse>(&query.into())
+        .is_ok()
+}
@@ -0,0 +1,64 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+
+use cw721::CustomMsg;
+
+use crate::error::ContractError;
+use crate::state::Cw721Contract;
+
+/// Implement this to handle a custom contract's `ExecuteMsg::Extension`
+/// messages, then pass it to `Cw721Contract::execute_with_extension`.
+/// Contracts with `QueryMsg::Extension` messages also implement `query_extension`,
+/// and pass it to `Cw721Contract::query_with_extension`. Otherwise they're rejected.
+/// The handler gets the contract itself, for access to the token storage
+pub trait ExtensionHandler<T, C, E, Q>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+    E: CustomMsg,
+    Q: CustomMsg,
+{
+    fn execute_extension(
+        &self,
+        contract: &Cw721Contract<T, C, E, Q>,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: E,
+    ) -> Result<Response<C>, ContractError>;
+
+    fn query_extension(
+        &self,
+        _contract: &Cw721Contract<T, C, E, Q>,
+        _deps: Deps,
+        _env: Env,
+        _msg: Q,
+    ) -> StdResult<Binary> {
+        Err(StdError::generic_err("extension queries not supported"))
+    }
+}
+
+/// Accepts and ignores every extension execute message, and rejects extension queries.
+/// Used by `Cw721Contract::execute` and `Cw721Contract::query`
+pub struct IgnoreExtension;
+
+impl<T, C, E, Q> ExtensionHandler<T, C, E, Q> for IgnoreExtension
+where
+    T: Serialize + DeserializeOwned + Clone,
+    C: CustomMsg,
+    E: CustomMsg,
+    Q: CustomMsg,
+{
+    fn execute_extension(
+        &self,
+        _contract: &Cw721Contract<T, C, E, Q>,
+        _deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        _msg: E,
+    ) -> Result<Response<C>, ContractError> {
+        Ok(Response::default())
+    }
+}
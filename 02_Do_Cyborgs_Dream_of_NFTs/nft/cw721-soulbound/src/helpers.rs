@@ -1,4 +1,4 @@
-use crate::{ExecuteMsg, QueryMsg};
+use crate::{DelegationsResponse, ExecuteMsg, MintersResponse, NftInfosResponse, QueryMsg};
 use cosmwasm_std::{
     to_binary, Addr, CosmosMsg, CustomMsg, QuerierWrapper, StdResult, WasmMsg, WasmQuery,
 };
@@ -92,6 +92,19 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
         Ok(res)
     }
 
+    pub fn delegations<T: Into<String>>(
+        &self,
+        querier: &QuerierWrapper,
+        token_id: T,
+        include_expired: Option<bool>,
+    ) -> StdResult<DelegationsResponse> {
+        let req = QueryMsg::Delegations {
+            token_id: token_id.into(),
+            include_expired,
+        };
+        self.query(querier, req)
+    }
+
     pub fn all_operators<T: Into<String>>(
         &self,
         querier: &QuerierWrapper,
@@ -116,6 +129,16 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
         Ok(res.count)
     }
 
+    pub fn minters(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<MintersResponse> {
+        let req = QueryMsg::Minters { start_after, limit };
+        self.query(querier, req)
+    }
+
     /// With metadata extension
     pub fn contract_info(&self, querier: &QuerierWrapper) -> StdResult<ContractInfoResponse> {
         let req = QueryMsg::ContractInfo {};
@@ -148,6 +171,33 @@ impl<Q: CustomMsg, E: CustomMsg> Cw721Contract<Q, E> {
         self.query(querier, req)
     }
 
+    /// With enumerable extension
+    pub fn all_nft_infos<U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NftInfosResponse<U>> {
+        let req = QueryMsg::AllNftInfos { start_after, limit };
+        self.query(querier, req)
+    }
+
+    /// With enumerable extension
+    pub fn nft_infos_by_owner<T: Into<String>, U: DeserializeOwned>(
+        &self,
+        querier: &QuerierWrapper,
+        owner: T,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NftInfosResponse<U>> {
+        let req = QueryMsg::NftInfosByOwner {
+            owner: owner.into(),
+            start_after,
+            limit,
+        };
+        self.query(querier, req)
+    }
+
     /// With enumerable extension
     pub fn tokens<T: Into<String>>(
         &self,
@@ -0,0 +1,168 @@
+#![cfg(test)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw721::{Cw721ReceiveMsg, OwnerOfResponse};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use crate::{Extension, ExecuteMsg, InstantiateMsg, MintMsg, QueryMsg};
+
+const MINTER: &str = "merlin";
+
+// A smart wallet that keeps the last token it was told it received,
+// and turns down any token that comes with a "reject" message
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+const RECEIVED: Item<Cw721ReceiveMsg> = Item::new("received");
+
+fn receiver_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ReceiverExecuteMsg,
+) -> StdResult<Response> {
+    let ReceiverExecuteMsg::ReceiveNft(received) = msg;
+    if received.msg == Binary::from(b"reject") {
+        return Err(StdError::generic_err("Token rejected"));
+    }
+    RECEIVED.save(deps.storage, &received)?;
+    Ok(Response::new())
+}
+
+fn receiver_instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn receiver_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_binary(&RECEIVED.may_load(deps.storage)?)
+}
+
+fn contract_receiver() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(receiver_execute, receiver_instantiate, receiver_query);
+    Box::new(contract)
+}
+
+fn contract_cw721() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::entry::execute,
+        crate::entry::instantiate,
+        crate::entry::query,
+    );
+    Box::new(contract)
+}
+
+fn mint_msg(token_id: &str, owner: &Addr, notify: Option<Binary>) -> ExecuteMsg<Extension, Empty> {
+    ExecuteMsg::Mint(MintMsg {
+        token_id: token_id.to_string(),
+        owner: owner.to_string(),
+        token_uri: None,
+        extension: None,
+        expires: None,
+        notify,
+    })
+}
+
+#[test]
+fn notifying_contract_owners() {
+    let mut app = App::default();
+    let minter = Addr::unchecked(MINTER);
+
+    let cw721_id = app.store_code(contract_cw721());
+    let msg = InstantiateMsg {
+        name: "Magic Power".to_string(),
+        symbol: "MGK".to_string(),
+        minter: MINTER.to_string(),
+        unique_owners: false,
+    };
+    let nft = app
+        .instantiate_contract(cw721_id, minter.clone(), &msg, &[], "cw721", None)
+        .unwrap();
+    let receiver_id = app.store_code(contract_receiver());
+    let receiver = app
+        .instantiate_contract(receiver_id, minter.clone(), &Empty {}, &[], "receiver", None)
+        .unwrap();
+
+    // contract owners are told what they've received, and by whom
+    let notify = Binary::from(b"welcome to camelot");
+    app.execute_contract(
+        minter.clone(),
+        nft.clone(),
+        &mint_msg("grail", &receiver, Some(notify.clone())),
+        &[],
+    )
+    .unwrap();
+    let received: Option<Cw721ReceiveMsg> = app
+        .wrap()
+        .query_wasm_smart(&receiver, &Empty {})
+        .unwrap();
+    assert_eq!(
+        received,
+        Some(Cw721ReceiveMsg {
+            sender: MINTER.to_string(),
+            token_id: "grail".to_string(),
+            msg: notify,
+        })
+    );
+
+    // other owners just get the token
+    let arthur = Addr::unchecked("arthur");
+    app.execute_contract(
+        minter.clone(),
+        nft.clone(),
+        &mint_msg("excalibur", &arthur, Some(Binary::from(b"hello"))),
+        &[],
+    )
+    .unwrap();
+    let owner: OwnerOfResponse = app
+        .wrap()
+        .query_wasm_smart(
+            &nft,
+            &QueryMsg::<Empty>::OwnerOf {
+                token_id: "excalibur".to_string(),
+                include_expired: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(owner.owner, arthur.to_string());
+
+    // a contract that turns its token down stops it being minted
+    app.execute_contract(
+        minter.clone(),
+        nft.clone(),
+        &mint_msg("lance", &receiver, Some(Binary::from(b"reject"))),
+        &[],
+    )
+    .unwrap_err();
+    let res: StdResult<OwnerOfResponse> = app.wrap().query_wasm_smart(
+        &nft,
+        &QueryMsg::<Empty>::OwnerOf {
+            token_id: "lance".to_string(),
+            include_expired: None,
+        },
+    );
+    assert!(res.is_err());
+
+    // unless it isn't asked
+    app.execute_contract(minter, nft, &mint_msg("lance", &receiver, None), &[])
+        .unwrap();
+    let received: Option<Cw721ReceiveMsg> = app
+        .wrap()
+        .query_wasm_smart(&receiver, &Empty {})
+        .unwrap();
+    assert_eq!(received.unwrap().token_id, "grail");
+}
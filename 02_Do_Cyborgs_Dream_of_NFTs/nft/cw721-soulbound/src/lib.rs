@@ -1,14 +1,20 @@
 mod contract_tests;
 mod error;
 mod execute;
+pub mod extension;
 pub mod helpers;
+mod integration_tests;
 pub mod msg;
 mod query;
 pub mod state;
 
 pub use crate::error::ContractError;
-pub use crate::msg::{ExecuteMsg, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
-pub use crate::state::Cw721Contract;
+pub use crate::extension::{ExtensionHandler, IgnoreExtension};
+pub use crate::msg::{
+    DelegationInfo, DelegationsResponse, ExecuteMsg, InstantiateMsg, MintMsg, MinterInfo,
+    MinterResponse, MintersResponse, NftInfoEntry, NftInfosResponse, QueryMsg,
+};
+pub use crate::state::{Cw721Contract, DelegationScope, ExtensionIndexFn};
 use cosmwasm_std::Empty;
 
 // This is a simple type to let us handle empty extensions
@@ -1,7 +1,10 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw721::Expiration;
+use cosmwasm_std::Binary;
+use cw721::{Expiration, NftInfoResponse};
+
+use crate::state::DelegationScope;
 
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -11,10 +14,14 @@ pub struct InstantiateMsg {
     /// Symbol of the NFT contract
     pub symbol: String,
 
-    /// The minter is the only one who can create new NFTs.
+    /// The first minter, who can create new NFTs without a quota.
     /// This is designed for a base NFT that is controlled by an external program
-    /// or contract. You will likely replace this with custom logic in custom NFTs
+    /// or contract. The sender becomes the admin, who can add and remove minters
     pub minter: String,
+
+    /// Allow each address to own only one token. Can't be changed later
+    #[serde(default)]
+    pub unique_owners: bool,
 }
 
 /// This is like Cw721ExecuteMsg but we add a Mint command for an owner
@@ -23,7 +30,8 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg<T, E> {
-    /// Allows operator to transfer / send the token from the owner's account.
+    /// Legacy cw721 approval. Tokens can't be transferred, so this delegates
+    /// `burn` to the spender, the same as `Delegate` with the `burn` scope.
     /// If expiration is set, then this allowance has a time/height limit
     Approve {
         spender: String,
@@ -31,13 +39,14 @@ pub enum ExecuteMsg<T, E> {
         expires: Option<Expiration>,
     },
     
-    /// Remove previously granted Approval
+    /// Remove a `burn` delegation granted by `Approve` or `Delegate`
     Revoke { 
         spender: String, 
         token_id: String 
     },
     
-    /// Allows operator to transfer / send any token from the owner's account.
+    /// Legacy cw721 approval. Allows operator to burn any token in the owner's account,
+    /// and to delegate `burn` to others.
     /// If expiration is set, then this allowance has a time/height limit
     ApproveAll {
         operator: String,
@@ -46,12 +55,46 @@ pub enum ExecuteMsg<T, E> {
     /// Remove previously granted ApproveAll permission
     RevokeAll { operator: String },
 
-    /// Mint a new NFT, can only be called by the contract minter
+    /// Allow `delegate` to act on the token within `scope`, until `expires` if set.
+    /// Replaces any delegation already granted to `delegate` for the same scope.
+    /// Only the owner can delegate, except operators can also delegate `burn`
+    Delegate {
+        token_id: String,
+        delegate: String,
+        scope: DelegationScope,
+        expires: Option<Expiration>,
+    },
+    /// Remove a delegation granted by `Delegate`
+    Undelegate {
+        token_id: String,
+        delegate: String,
+        scope: DelegationScope,
+    },
+
+    /// Mint a new NFT, can only be called by a contract minter
     Mint(MintMsg<T>),
 
+    /// Allow an address to mint, up to `quota` tokens if set. Updates the
+    /// quota of an existing minter. Can only be called by the admin
+    AddMinter { minter: String, quota: Option<u64> },
+    /// Stop an address from minting. Can only be called by the admin
+    RemoveMinter { minter: String },
+
     /// Burn an NFT the sender has access to
     Burn { token_id: String },
 
+    /// Replace an NFT's extension, can only be called by the minter that
+    /// issued it or the admin
+    UpdateMetadata { token_id: String, extension: T },
+
+    /// Revoke an NFT, can only be called by the minter that issued it or the admin.
+    /// The token is kept for audit, but is no longer valid
+    RevokeToken { token_id: String, reason: String },
+
+    /// Recount the stored tokens, fixing token counts that have gone out of sync.
+    /// Can only be called by the admin
+    ReconcileTokens {},
+
     /// Extension msg
     Extension { msg: E },
 }
@@ -69,6 +112,12 @@ pub struct MintMsg<T> {
     pub token_uri: Option<String>,
     /// Any custom extension used by this contract
     pub extension: T,
+    /// When the token stops being valid, if ever
+    #[serde(default)]
+    pub expires: Option<Expiration>,
+    /// If the owner is a contract, it's sent a `ReceiveNft` callback with this message
+    #[serde(default)]
+    pub notify: Option<Binary>,
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -104,6 +153,14 @@ pub enum QueryMsg<Q> {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// Everyone the token has been delegated to, and for what.
+    /// Unlike `Approvals`, this includes every scope.
+    /// Return type: `DelegationsResponse`
+    Delegations {
+        token_id: String,
+        /// unset or false will filter out expired delegations, you must set to true to see them
+        include_expired: Option<bool>,
+    },
     /// Total number of tokens issued
     NumTokens {},
 
@@ -140,9 +197,33 @@ pub enum QueryMsg<Q> {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// With Enumerable extension.
+    /// Requires pagination. Lists tokens along with their `NftInfo`,
+    /// saving clients a query per token.
+    /// Return type: NftInfosResponse.
+    AllNftInfos {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// With Enumerable extension.
+    /// Like `AllNftInfos`, but only for tokens owned by the given address.
+    /// Return type: NftInfosResponse.
+    NftInfosByOwner {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
-    // Return the minter
+    /// The minter named at instantiation, for clients that predate `Minters`.
+    /// It may no longer be able to mint; use `Minters` to see who can
+    /// Return type: `MinterResponse`
     Minter {},
+    /// Lists everyone who can mint, with their quotas
+    /// Return type: `MintersResponse`
+    Minters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 
     /// Extension query
     Extension {
@@ -156,3 +237,47 @@ pub enum QueryMsg<Q> {
 pub struct MinterResponse {
     pub minter: String,
 }
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MinterInfo {
+    pub minter: String,
+    /// Most tokens this minter can mint, if limited
+    pub quota: Option<u64>,
+    /// Tokens minted so far
+    pub minted: u64,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MintersResponse {
+    pub minters: Vec<MinterInfo>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NftInfoEntry<T> {
+    pub token_id: String,
+    pub info: NftInfoResponse<T>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct NftInfosResponse<T> {
+    /// Ordered by `token_id`. Pass the last one as `start_after` to fetch the next page
+    pub tokens: Vec<NftInfoEntry<T>>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DelegationInfo {
+    pub delegate: String,
+    pub scope: DelegationScope,
+    pub expires: Expiration,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct DelegationsResponse {
+    pub delegations: Vec<DelegationInfo>,
+}
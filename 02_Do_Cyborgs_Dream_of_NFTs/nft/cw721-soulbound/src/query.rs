@@ -11,8 +11,12 @@ use cw721::{
 use cw_storage_plus::Bound;
 use cw_utils::maybe_addr;
 
-use crate::msg::{MinterResponse, QueryMsg};
-use crate::state::{Approval, Cw721Contract, TokenInfo};
+use crate::extension::{ExtensionHandler, IgnoreExtension};
+use crate::msg::{
+    DelegationInfo, DelegationsResponse, MinterInfo, MinterResponse, MintersResponse, NftInfoEntry,
+    NftInfosResponse, QueryMsg,
+};
+use crate::state::{Approval, Cw721Contract, DelegationScope, TokenInfo};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 100;
@@ -30,12 +34,20 @@ where
 
     fn num_tokens(&self, deps: Deps) -> StdResult<NumTokensResponse> {
         let count = self.token_count(deps.storage)?;
-        Ok(NumTokensResponse { count })
+        let supply = self.token_supply(deps.storage)?;
+        Ok(NumTokensResponse {
+            count,
+            minted: supply.minted,
+            burned: supply.burned,
+            revoked: supply.revoked,
+            active: count.saturating_sub(supply.revoked),
+        })
     }
 
-    fn nft_info(&self, deps: Deps, token_id: String) -> StdResult<NftInfoResponse<T>> {
+    fn nft_info(&self, deps: Deps, env: Env, token_id: String) -> StdResult<NftInfoResponse<T>> {
         let info = self.tokens.load(deps.storage, &token_id)?;
         Ok(NftInfoResponse {
+            validity: info.validity(&env.block),
             token_uri: info.token_uri,
             extension: info.extension,
         })
@@ -52,6 +64,7 @@ where
         Ok(OwnerOfResponse {
             owner: info.owner.to_string(),
             approvals: humanize_approvals(&env.block, &info, include_expired),
+            validity: info.validity(&env.block),
         })
     }
 
@@ -105,7 +118,7 @@ where
         let filtered: Vec<_> = token
             .approvals
             .into_iter()
-            .filter(|t| t.spender == spender)
+            .filter(|t| t.spender == spender && t.scope == DelegationScope::Burn)
             .filter(|t| include_expired || !t.is_expired(&env.block))
             .map(|a| cw721::Approval {
                 spender: a.spender.into_string(),
@@ -122,7 +135,8 @@ where
         Ok(ApprovalResponse { approval })
     }
 
-    /// approvals returns all approvals owner given access to
+    /// approvals returns all approvals owner given access to.
+    /// cw721 approvals can't carry a scope, so these are just the `burn` delegations
     fn approvals(
         &self,
         deps: Deps,
@@ -134,6 +148,7 @@ where
         let approvals: Vec<_> = token
             .approvals
             .into_iter()
+            .filter(|t| t.scope == DelegationScope::Burn)
             .filter(|t| include_expired || !t.is_expired(&env.block))
             .map(|a| cw721::Approval {
                 spender: a.spender.into_string(),
@@ -194,14 +209,17 @@ where
         include_expired: bool,
     ) -> StdResult<AllNftInfoResponse<T>> {
         let info = self.tokens.load(deps.storage, &token_id)?;
+        let validity = info.validity(&env.block);
         Ok(AllNftInfoResponse {
             access: OwnerOfResponse {
                 owner: info.owner.to_string(),
                 approvals: humanize_approvals(&env.block, &info, include_expired),
+                validity: validity.clone(),
             },
             info: NftInfoResponse {
                 token_uri: info.token_uri,
                 extension: info.extension,
+                validity,
             },
         })
     }
@@ -214,18 +232,129 @@ where
     E: CustomMsg,
     Q: CustomMsg,
 {
+    /// The minter named at instantiation, even if it's since been removed.
+    /// Contracts that didn't record one report their admin instead
     pub fn minter(&self, deps: Deps) -> StdResult<MinterResponse> {
-        let minter_addr = self.minter.load(deps.storage)?;
+        let minter_addr = match self.initial_minter.may_load(deps.storage)? {
+            Some(minter) => minter,
+            None => self.admin.load(deps.storage)?,
+        };
         Ok(MinterResponse {
             minter: minter_addr.to_string(),
         })
     }
 
+    pub fn minters(
+        &self,
+        deps: Deps,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<MintersResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_addr = maybe_addr(deps.api, start_after)?;
+        let start = start_addr.as_ref().map(Bound::exclusive);
+
+        let minters: StdResult<Vec<_>> = self
+            .minters
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(minter, quota)| MinterInfo {
+                    minter: minter.into_string(),
+                    quota: quota.quota,
+                    minted: quota.minted,
+                })
+            })
+            .collect();
+        Ok(MintersResponse { minters: minters? })
+    }
+
+    pub fn delegations(
+        &self,
+        deps: Deps,
+        env: Env,
+        token_id: String,
+        include_expired: bool,
+    ) -> StdResult<DelegationsResponse> {
+        let token = self.tokens.load(deps.storage, &token_id)?;
+        let delegations = token
+            .approvals
+            .into_iter()
+            .filter(|apr| include_expired || !apr.is_expired(&env.block))
+            .map(|apr| DelegationInfo {
+                delegate: apr.spender.into_string(),
+                scope: apr.scope,
+                expires: apr.expires,
+            })
+            .collect();
+        Ok(DelegationsResponse { delegations })
+    }
+
+    pub fn all_nft_infos(
+        &self,
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NftInfosResponse<T>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let tokens: StdResult<Vec<_>> = self
+            .tokens
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(token_id, info)| nft_info_entry(&env.block, token_id, info)))
+            .collect();
+        Ok(NftInfosResponse { tokens: tokens? })
+    }
+
+    pub fn nft_infos_by_owner(
+        &self,
+        deps: Deps,
+        env: Env,
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<NftInfosResponse<T>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+
+        let owner_addr = deps.api.addr_validate(&owner)?;
+        let tokens: StdResult<Vec<_>> = self
+            .tokens
+            .idx
+            .owner
+            .prefix(owner_addr)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(token_id, info)| nft_info_entry(&env.block, token_id, info)))
+            .collect();
+        Ok(NftInfosResponse { tokens: tokens? })
+    }
+
     pub fn query(&self, deps: Deps, env: Env, msg: QueryMsg<Q>) -> StdResult<Binary> {
+        self.query_with_extension(deps, env, msg, &IgnoreExtension)
+    }
+
+    /// Like `query`, but passes `QueryMsg::Extension` messages on to `handler`
+    pub fn query_with_extension<H>(
+        &self,
+        deps: Deps,
+        env: Env,
+        msg: QueryMsg<Q>,
+        handler: &H,
+    ) -> StdResult<Binary>
+    where
+        H: ExtensionHandler<T, C, E, Q>,
+    {
         match msg {
             QueryMsg::Minter {} => to_binary(&self.minter(deps)?),
+            QueryMsg::Minters { start_after, limit } => {
+                to_binary(&self.minters(deps, start_after, limit)?)
+            }
             QueryMsg::ContractInfo {} => to_binary(&self.contract_info(deps)?),
-            QueryMsg::NftInfo { token_id } => to_binary(&self.nft_info(deps, token_id)?),
+            QueryMsg::NftInfo { token_id } => to_binary(&self.nft_info(deps, env, token_id)?),
             QueryMsg::OwnerOf {
                 token_id,
                 include_expired,
@@ -263,6 +392,14 @@ where
             QueryMsg::AllTokens { start_after, limit } => {
                 to_binary(&self.all_tokens(deps, start_after, limit)?)
             }
+            QueryMsg::AllNftInfos { start_after, limit } => {
+                to_binary(&self.all_nft_infos(deps, env, start_after, limit)?)
+            }
+            QueryMsg::NftInfosByOwner {
+                owner,
+                start_after,
+                limit,
+            } => to_binary(&self.nft_infos_by_owner(deps, env, owner, start_after, limit)?),
             QueryMsg::Approval {
                 token_id,
                 spender,
@@ -280,11 +417,31 @@ where
             } => {
                 to_binary(&self.approvals(deps, env, token_id, include_expired.unwrap_or(false))?)
             }
-            QueryMsg::Extension { msg: _ } => Ok(Binary::default()),
+            QueryMsg::Delegations {
+                token_id,
+                include_expired,
+            } => to_binary(&self.delegations(
+                deps,
+                env,
+                token_id,
+                include_expired.unwrap_or(false),
+            )?),
+            QueryMsg::Extension { msg } => handler.query_extension(self, deps, env, msg),
         }
     }
 }
 
+fn nft_info_entry<T>(block: &BlockInfo, token_id: String, info: TokenInfo<T>) -> NftInfoEntry<T> {
+    NftInfoEntry {
+        info: NftInfoResponse {
+            validity: info.validity(block),
+            token_uri: info.token_uri,
+            extension: info.extension,
+        },
+        token_id,
+    }
+}
+
 fn parse_approval(item: StdResult<(Addr, Expiration)>) -> StdResult<cw721::Approval> {
     item.map(|(spender, expires)| cw721::Approval {
         spender: spender.to_string(),
@@ -299,6 +456,7 @@ fn humanize_approvals<T>(
 ) -> Vec<cw721::Approval> {
     info.approvals
         .iter()
+        .filter(|apr| apr.scope == DelegationScope::Burn)
         .filter(|apr| include_expired || !apr.is_expired(block))
         .map(humanize_approval)
         .collect()
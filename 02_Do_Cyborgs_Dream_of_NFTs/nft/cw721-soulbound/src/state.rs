@@ -1,12 +1,18 @@
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::marker::PhantomData;
 
 use cosmwasm_std::{Addr, BlockInfo, StdResult, Storage};
 
-use cw721::{ContractInfoResponse, CustomMsg, Cw721, Expiration};
-use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw721::{ContractInfoResponse, CustomMsg, Cw721, Expiration, TokenValidity};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
+
+use crate::error::ContractError;
+
+// Where the single minter was stored, before minters were added
+const LEGACY_MINTER_KEY: &str = "minter";
 
 pub struct Cw721Contract<'a, T, C, E, Q>
 where
@@ -15,9 +21,17 @@ where
     E: CustomMsg,
 {
     pub contract_info: Item<'a, ContractInfoResponse>,
-    pub minter: Item<'a, Addr>,
+    /// Can add and remove minters
+    pub admin: Item<'a, Addr>,
+    /// Everyone who can mint, and how many tokens they can and have minted
+    pub minters: Map<'a, &'a Addr, MinterQuota>,
+    /// The minter named at instantiation, reported by the `Minter {}` query
+    pub initial_minter: Item<'a, Addr>,
+    /// Tokens currently in existence
     pub token_count: Item<'a, u64>,
-    /// Stored as (granter, operator) giving operator full control over granter's account
+    /// Running totals of tokens minted, burned and revoked
+    pub token_supply: Item<'a, TokenSupply>,
+    /// Stored as (granter, operator) letting operator burn any of granter's tokens
     pub operators: Map<'a, (&'a Addr, &'a Addr), Expiration>,
     pub tokens: IndexedMap<'a, &'a str, TokenInfo<T>, TokenIndexes<'a, T>>,
 
@@ -43,13 +57,32 @@ where
     Q: CustomMsg,
 {
     fn default() -> Self {
+        Self::with_indexes(vec![])
+    }
+}
+
+impl<T, C, E, Q> Cw721Contract<'static, T, C, E, Q>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    E: CustomMsg,
+    Q: CustomMsg,
+{
+    /// Like `default`, but also indexes tokens by their extension. Each index
+    /// is given as its storage key and a function returning a token's index value
+    pub fn with_indexes(extension_indexes: Vec<(&'static str, ExtensionIndexFn<T>)>) -> Self {
         Self::new(
             "nft_info",
-            "minter",
+            "admin",
+            "minters",
+            "initial_minter",
             "num_tokens",
+            "token_supply",
             "operators",
             "tokens",
             "tokens__owner",
+            "unique_owners",
+            "tokens__unique_owner",
+            extension_indexes,
         )
     }
 }
@@ -60,21 +93,39 @@ where
     E: CustomMsg,
     Q: CustomMsg,
 {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         contract_key: &'a str,
-        minter_key: &'a str,
+        admin_key: &'a str,
+        minters_key: &'a str,
+        initial_minter_key: &'a str,
         token_count_key: &'a str,
+        token_supply_key: &'a str,
         operator_key: &'a str,
         tokens_key: &'a str,
         tokens_owner_key: &'a str,
+        unique_owners_key: &'a str,
+        tokens_unique_owner_key: &'a str,
+        extension_indexes: Vec<(&'a str, ExtensionIndexFn<T>)>,
     ) -> Self {
         let indexes = TokenIndexes {
             owner: MultiIndex::new(token_owner_idx, tokens_key, tokens_owner_key),
+            unique_owner: UniqueOwnerIndex {
+                enabled: Item::new(unique_owners_key),
+                index: UniqueIndex::new(token_owner_idx, tokens_unique_owner_key),
+            },
+            extension: extension_indexes
+                .into_iter()
+                .map(|(key, idx_fn)| (key, MultiIndex::new(idx_fn, tokens_key, key)))
+                .collect(),
         };
         Self {
             contract_info: Item::new(contract_key),
-            minter: Item::new(minter_key),
+            admin: Item::new(admin_key),
+            minters: Map::new(minters_key),
+            initial_minter: Item::new(initial_minter_key),
             token_count: Item::new(token_count_key),
+            token_supply: Item::new(token_supply_key),
             operators: Map::new(operator_key),
             tokens: IndexedMap::new(tokens_key, indexes),
             _custom_response: PhantomData,
@@ -83,28 +134,85 @@ where
         }
     }
 
+    /// Contracts instantiated before there could be several minters stored
+    /// their only minter under `"minter"`. That minter becomes the admin, and
+    /// a minter without a quota, so it can still mint and manage minters
+    pub fn migrate_legacy_minter(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        let legacy_minter: Item<Addr> = Item::new(LEGACY_MINTER_KEY);
+        if let Some(minter) = legacy_minter.may_load(storage)? {
+            let quota = MinterQuota {
+                quota: None,
+                minted: self.token_count(storage)?,
+            };
+            self.admin.save(storage, &minter)?;
+            self.minters.save(storage, &minter, &quota)?;
+            self.initial_minter.save(storage, &minter)?;
+            legacy_minter.remove(storage);
+        }
+        Ok(())
+    }
+
     pub fn token_count(&self, storage: &dyn Storage) -> StdResult<u64> {
         Ok(self.token_count.may_load(storage)?.unwrap_or_default())
     }
 
-    pub fn increment_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
-        let val = self.token_count(storage)? + 1;
+    pub fn token_supply(&self, storage: &dyn Storage) -> StdResult<TokenSupply> {
+        Ok(self.token_supply.may_load(storage)?.unwrap_or_default())
+    }
+
+    pub fn increment_tokens(&self, storage: &mut dyn Storage) -> Result<u64, ContractError> {
+        let val = checked(self.token_count(storage)?.checked_add(1))?;
         self.token_count.save(storage, &val)?;
+        let mut supply = self.token_supply(storage)?;
+        supply.minted = checked(supply.minted.checked_add(1))?;
+        self.token_supply.save(storage, &supply)?;
         Ok(val)
     }
 
-    pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> StdResult<u64> {
-        let val = self.token_count(storage)? - 1;
+    pub fn decrement_tokens(&self, storage: &mut dyn Storage) -> Result<u64, ContractError> {
+        let val = checked(self.token_count(storage)?.checked_sub(1))?;
         self.token_count.save(storage, &val)?;
+        let mut supply = self.token_supply(storage)?;
+        supply.burned = checked(supply.burned.checked_add(1))?;
+        self.token_supply.save(storage, &supply)?;
         Ok(val)
     }
+
+    pub fn increment_revoked(&self, storage: &mut dyn Storage) -> Result<u64, ContractError> {
+        let mut supply = self.token_supply(storage)?;
+        supply.revoked = checked(supply.revoked.checked_add(1))?;
+        self.token_supply.save(storage, &supply)?;
+        Ok(supply.revoked)
+    }
+}
+
+fn checked(val: Option<u64>) -> Result<u64, ContractError> {
+    val.ok_or(ContractError::InconsistentTokenCount {})
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct TokenSupply {
+    /// Tokens ever minted
+    pub minted: u64,
+    /// Tokens ever burned
+    pub burned: u64,
+    /// Tokens revoked, which are never burned
+    pub revoked: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterQuota {
+    /// Most tokens this minter can mint, if limited
+    pub quota: Option<u64>,
+    /// Tokens minted so far
+    pub minted: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TokenInfo<T> {
     /// The owner of the newly minted NFT
     pub owner: Addr,
-    /// Approvals are stored here, as we clear them all upon transfer and cannot accumulate much
+    /// Delegations are stored here, as there's at most one per spender and scope
     pub approvals: Vec<Approval>,
 
     /// Universal resource identifier for this NFT
@@ -114,20 +222,77 @@ pub struct TokenInfo<T> {
 
     /// You can add any custom metadata here when you extend cw721-base
     pub extension: T,
+
+    /// Why the minter revoked this token, if it has been.
+    /// Revoked tokens are kept for audit, rather than burned
+    #[serde(default)]
+    pub revoked: Option<String>,
+    /// When the token stops being valid, if ever
+    #[serde(default)]
+    pub expires: Option<Expiration>,
+    /// The minter that minted this token. Only it, while it's still a minter,
+    /// or the admin can update or revoke the token. Unset for tokens minted
+    /// before issuers were recorded, which only the admin can update or revoke
+    #[serde(default)]
+    pub issuer: Option<Addr>,
+}
+
+impl<T> TokenInfo<T> {
+    pub fn validity(&self, block: &BlockInfo) -> TokenValidity {
+        if let Some(reason) = &self.revoked {
+            return TokenValidity::Revoked { reason: reason.clone() };
+        }
+        match self.expires {
+            Some(expires) if expires.is_expired(block) => TokenValidity::Expired { expires },
+            _ => TokenValidity::Valid {},
+        }
+    }
+}
+
+/// What a delegate is allowed to do with someone else's token.
+/// Soulbound tokens can't be transferred, so there's no transfer scope
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegationScope {
+    /// Burn the token. Approvals stored before delegations were scoped could only burn
+    #[default]
+    Burn,
+    /// Recover the token for its owner, e.g. after they've lost their keys
+    Recover,
+    /// Update the token's image
+    UpdateImage,
+}
+
+impl fmt::Display for DelegationScope {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DelegationScope::Burn => write!(f, "burn"),
+            DelegationScope::Recover => write!(f, "recover"),
+            DelegationScope::UpdateImage => write!(f, "update_image"),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
 pub struct Approval {
-    /// Account that can transfer/send the token
+    /// Account the token is delegated to
     pub spender: Addr,
     /// When the Approval expires (maybe Expiration::never)
     pub expires: Expiration,
+    /// What the spender can do with the token
+    #[serde(default)]
+    pub scope: DelegationScope,
 }
 
 impl Approval {
     pub fn is_expired(&self, block: &BlockInfo) -> bool {
         self.expires.is_expired(block)
     }
+
+    /// Whether this lets `spender` act within `scope` right now
+    pub fn grants(&self, block: &BlockInfo, spender: &Addr, scope: DelegationScope) -> bool {
+        &self.spender == spender && self.scope == scope && !self.is_expired(block)
+    }
 }
 
 pub struct TokenIndexes<'a, T>
@@ -135,6 +300,22 @@ where
     T: Serialize + DeserializeOwned + Clone,
 {
     pub owner: MultiIndex<'a, Addr, TokenInfo<T>, String>,
+    pub unique_owner: UniqueOwnerIndex<'a, T>,
+    /// Indexes over extension fields, keyed by their storage key
+    pub extension: Vec<(&'a str, ExtensionIndex<'a, T>)>,
+}
+
+impl<'a, T> TokenIndexes<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    /// Look up an extension index by its storage key
+    pub fn extension(&self, key: &str) -> Option<&ExtensionIndex<'a, T>> {
+        self.extension
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, idx)| idx)
+    }
 }
 
 impl<'a, T> IndexList<TokenInfo<T>> for TokenIndexes<'a, T>
@@ -142,11 +323,61 @@ where
     T: Serialize + DeserializeOwned + Clone,
 {
     fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TokenInfo<T>>> + '_> {
-        let v: Vec<&dyn Index<TokenInfo<T>>> = vec![&self.owner];
+        let mut v: Vec<&dyn Index<TokenInfo<T>>> = vec![&self.owner, &self.unique_owner];
+        v.extend(self.extension.iter().map(|(_, idx)| idx as &dyn Index<TokenInfo<T>>));
         Box::new(v.into_iter())
     }
 }
 
+/// Limits each address to owning a single token, if enabled at instantiation.
+/// While disabled, tokens aren't indexed at all
+pub struct UniqueOwnerIndex<'a, T> {
+    pub enabled: Item<'a, bool>,
+    pub index: UniqueIndex<'a, Addr, TokenInfo<T>, String>,
+}
+
+impl<'a, T> UniqueOwnerIndex<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn is_enabled(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.enabled.may_load(storage)?.unwrap_or_default())
+    }
+
+    /// Whether `owner` can't be given another token
+    pub fn owns_token(&self, storage: &dyn Storage, owner: &Addr) -> StdResult<bool> {
+        if !self.is_enabled(storage)? {
+            return Ok(false);
+        }
+        Ok(self.index.item(storage, owner.clone())?.is_some())
+    }
+}
+
+impl<'a, T> Index<TokenInfo<T>> for UniqueOwnerIndex<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    fn save(&self, store: &mut dyn Storage, pk: &[u8], data: &TokenInfo<T>) -> StdResult<()> {
+        if !self.is_enabled(store)? {
+            return Ok(());
+        }
+        self.index.save(store, pk, data)
+    }
+
+    fn remove(&self, store: &mut dyn Storage, pk: &[u8], old_data: &TokenInfo<T>) -> StdResult<()> {
+        if !self.is_enabled(store)? {
+            return Ok(());
+        }
+        self.index.remove(store, pk, old_data)
+    }
+}
+
+/// Tokens filed under a value taken from their extension
+pub type ExtensionIndex<'a, T> = MultiIndex<'a, String, TokenInfo<T>, String>;
+
+/// Maps a token to the value an extension index files it under
+pub type ExtensionIndexFn<T> = fn(&TokenInfo<T>) -> String;
+
 pub fn token_owner_idx<T>(d: &TokenInfo<T>) -> Addr {
     d.owner.clone()
 }
@@ -1,14 +1,17 @@
 use std::env::current_dir;
 use std::fs::create_dir_all;
 
-use cosmwasm_std::Empty;
 use cosmwasm_schema::{export_schema, export_schema_with_title, remove_schemas, schema_for};
 
 use cw721::{
     AllNftInfoResponse, ApprovalResponse, ApprovalsResponse, ContractInfoResponse, NftInfoResponse,
     NumTokensResponse, OperatorsResponse, OwnerOfResponse, TokensResponse,
 };
-use passport_token::{ExecuteMsg, Extension, InstantiateMsg, MinterResponse, QueryMsg};
+use passport_token::{
+    DelegationsResponse, ExecuteMsg, Extension, InstantiateMsg, MigrateMsg, MinterResponse,
+    MintersResponse, NftInfosResponse, PassportQueryMsg, PassportResponse, PassportsResponse,
+    QueryMsg, RecoveryResponse, VisasResponse,
+};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -18,7 +21,8 @@ fn main() {
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema_with_title(&schema_for!(ExecuteMsg), &out_dir, "ExecuteMsg");
-    export_schema(&schema_for!(QueryMsg<Empty>), &out_dir);
+    export_schema_with_title(&schema_for!(QueryMsg<PassportQueryMsg>), &out_dir, "QueryMsg");
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema_with_title(
         &schema_for!(AllNftInfoResponse<Extension>),
         &out_dir,
@@ -26,14 +30,25 @@ fn main() {
     );
     export_schema(&schema_for!(ApprovalResponse), &out_dir);
     export_schema(&schema_for!(ApprovalsResponse), &out_dir);
+    export_schema(&schema_for!(DelegationsResponse), &out_dir);
     export_schema(&schema_for!(OperatorsResponse), &out_dir);
     export_schema(&schema_for!(ContractInfoResponse), &out_dir);
     export_schema(&schema_for!(MinterResponse), &out_dir);
+    export_schema(&schema_for!(MintersResponse), &out_dir);
+    export_schema(&schema_for!(VisasResponse), &out_dir);
+    export_schema(&schema_for!(PassportResponse), &out_dir);
+    export_schema(&schema_for!(PassportsResponse), &out_dir);
+    export_schema(&schema_for!(RecoveryResponse), &out_dir);
     export_schema_with_title(
         &schema_for!(NftInfoResponse<Extension>),
         &out_dir,
         "NftInfoResponse",
     );
+    export_schema_with_title(
+        &schema_for!(NftInfosResponse<Extension>),
+        &out_dir,
+        "NftInfosResponse",
+    );
     export_schema(&schema_for!(NumTokensResponse), &out_dir);
     export_schema(&schema_for!(OwnerOfResponse), &out_dir);
     export_schema(&schema_for!(TokensResponse), &out_dir);
@@ -0,0 +1,441 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError,
+    StdResult, Timestamp,
+};
+use cw721::{CustomMsg, TokenValidity};
+use cw721_soulbound::state::TokenInfo;
+use cw721_soulbound::{ContractError, Cw721Contract, DelegationScope, ExtensionHandler};
+use cw_storage_plus::{Bound, Map};
+
+use crate::recovery::{
+    approve_recovery, cancel_recovery, complete_recovery, recovery, request_recovery,
+    set_recovery_guardians,
+};
+use crate::{Extension, Metadata};
+
+// Storage keys of the passport indexes, alongside the `owner` index
+pub const IDENTITY_INDEX: &str = "tokens__identity";
+pub const SPECIES_INDEX: &str = "tokens__species";
+pub const ISSUER_INDEX: &str = "tokens__issuer";
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PassportExecuteMsg {
+    /// Stamp a visa into a passport as its traveler passes through a JumpRing.
+    /// The issuing JumpRing and the admin can stamp any visa. Any other
+    /// minter can only stamp an entry for a traveler in transit, or an exit
+    /// for a traveler it last stamped in
+    StampVisa {
+        token_id: String,
+        planet: String,
+        direction: VisaDirection,
+    },
+    /// Replace a passport's image. Can only be called by the passport's owner,
+    /// or someone they've delegated `update_image` to
+    UpdateImage {
+        token_id: String,
+        image: Option<String>,
+    },
+    /// Choose who can recover a passport if its traveler loses their keys, and how
+    /// many of them must agree. Can only be called by the passport's owner
+    SetRecoveryGuardians {
+        token_id: String,
+        guardians: Vec<String>,
+        threshold: u32,
+    },
+    /// Approve moving a passport to `new_identity`. Can only be called by its guardians,
+    /// and the passport is moved as soon as enough of them have approved
+    ApproveRecovery {
+        token_id: String,
+        new_identity: String,
+    },
+    /// Start the timelock on moving a passport to `new_identity`. Can only be called
+    /// by the JumpRing that issued it, its owner, or someone they've delegated `recover` to
+    RequestRecovery {
+        token_id: String,
+        new_identity: String,
+    },
+    /// Move a passport to `new_identity` once its recovery timelock has passed.
+    /// Can only be called by the JumpRing that issued it
+    CompleteRecovery {
+        token_id: String,
+        new_identity: String,
+    },
+    /// Stop a pending recovery. Can only be called by the passport's owner
+    CancelRecovery { token_id: String },
+    /// Save a page of passports again, ordered by `token_id`, filing passports minted
+    /// before the passport indexes existed under them. Call it after migrating, passing
+    /// the returned `last_token_id` as `start_after`, until it reports `done`.
+    /// Can only be called by the admin
+    ReindexPassports {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+impl CustomMsg for PassportExecuteMsg {}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PassportQueryMsg {
+    /// Visas stamped into a passport, oldest first
+    /// Return type: `VisasResponse`
+    Visas {
+        token_id: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// The passport proving `address`'s identity, if there is one
+    /// Return type: `PassportResponse`
+    PassportByIdentity { address: String },
+    /// Passports of travelers of a species, ordered by `token_id`
+    /// Return type: `PassportsResponse`
+    PassportsBySpecies {
+        species: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Passports issued by a JumpRing, ordered by `token_id`
+    /// Return type: `PassportsResponse`
+    PassportsByIssuer {
+        issuer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// A passport's recovery guardians, and any recovery pending for it
+    /// Return type: `RecoveryResponse`
+    Recovery { token_id: String },
+}
+
+impl CustomMsg for PassportQueryMsg {}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum VisaDirection {
+    Entry,
+    Exit,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct Visa {
+    pub portal: Addr,               // JumpRing that stamped the visa
+    pub planet: String,             // Planet the JumpRing is on
+    pub direction: VisaDirection,
+    pub block_time: Timestamp,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VisaEntry {
+    pub stamp: u64,                 // Pass as `start_after` to fetch the next page
+    pub visa: Visa,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct VisasResponse {
+    pub visas: Vec<VisaEntry>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PassportInfo {
+    pub token_id: String,
+    pub owner: Addr,
+    pub validity: TokenValidity,
+    pub extension: Extension,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PassportResponse {
+    pub passport: Option<PassportInfo>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PassportsResponse {
+    pub passports: Vec<PassportInfo>,
+}
+
+// Visas are kept apart from the passport's metadata, so passports
+// stay small no matter how far their travelers have been.
+// Keyed by `token_id` and stamp number
+pub const VISAS: Map<(&str, u64), Visa> = Map::new("visas");
+
+// Number of visas stamped into each passport so far
+pub const VISA_COUNTS: Map<&str, u64> = Map::new("visa_counts");
+
+/// Handles passport-specific `ExecuteMsg::Extension` messages
+pub struct PassportExtension;
+
+impl ExtensionHandler<Extension, Empty, PassportExecuteMsg, PassportQueryMsg> for PassportExtension {
+    fn execute_extension(
+        &self,
+        contract: &Cw721Contract<Extension, Empty, PassportExecuteMsg, PassportQueryMsg>,
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        msg: PassportExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        match msg {
+            PassportExecuteMsg::StampVisa { token_id, planet, direction } => {
+                stamp_visa(contract, deps, env, info, token_id, planet, direction)
+            }
+            PassportExecuteMsg::UpdateImage { token_id, image } => {
+                update_image(contract, deps, env, info, token_id, image)
+            }
+            PassportExecuteMsg::SetRecoveryGuardians { token_id, guardians, threshold } => {
+                set_recovery_guardians(contract, deps, info, token_id, guardians, threshold)
+            }
+            PassportExecuteMsg::ApproveRecovery { token_id, new_identity } => {
+                approve_recovery(contract, deps, env, info, token_id, new_identity)
+            }
+            PassportExecuteMsg::RequestRecovery { token_id, new_identity } => {
+                request_recovery(contract, deps, env, info, token_id, new_identity)
+            }
+            PassportExecuteMsg::CompleteRecovery { token_id, new_identity } => {
+                complete_recovery(contract, deps, env, info, token_id, new_identity)
+            }
+            PassportExecuteMsg::CancelRecovery { token_id } => {
+                cancel_recovery(contract, deps, info, token_id)
+            }
+            PassportExecuteMsg::ReindexPassports { start_after, limit } => {
+                reindex_passports(contract, deps, info, start_after, limit)
+            }
+        }
+    }
+
+    fn query_extension(
+        &self,
+        contract: &Cw721Contract<Extension, Empty, PassportExecuteMsg, PassportQueryMsg>,
+        deps: Deps,
+        env: Env,
+        msg: PassportQueryMsg,
+    ) -> StdResult<Binary> {
+        match msg {
+            PassportQueryMsg::Visas { token_id, start_after, limit } => {
+                to_binary(&visas(deps, token_id, start_after, limit)?)
+            }
+            PassportQueryMsg::PassportByIdentity { address } => {
+                let address = deps.api.addr_validate(&address)?;
+                let passport = passports_by(
+                    contract, deps, &env, IDENTITY_INDEX, address.into(), None, Some(1),
+                )?
+                .pop();
+                to_binary(&PassportResponse { passport })
+            }
+            PassportQueryMsg::PassportsBySpecies { species, start_after, limit } => {
+                let passports = passports_by(
+                    contract, deps, &env, SPECIES_INDEX, species, start_after, limit,
+                )?;
+                to_binary(&PassportsResponse { passports })
+            }
+            PassportQueryMsg::PassportsByIssuer { issuer, start_after, limit } => {
+                let issuer = deps.api.addr_validate(&issuer)?;
+                let passports = passports_by(
+                    contract, deps, &env, ISSUER_INDEX, issuer.into(), start_after, limit,
+                )?;
+                to_binary(&PassportsResponse { passports })
+            }
+            PassportQueryMsg::Recovery { token_id } => to_binary(&recovery(deps, token_id)?),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn stamp_visa(
+    contract: &Cw721Contract<Extension, Empty, PassportExecuteMsg, PassportQueryMsg>,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    planet: String,
+    direction: VisaDirection,
+) -> Result<Response, ContractError> {
+    // Revoked passports aren't good for travel
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    if token.revoked.is_some() {
+        return Err(ContractError::Revoked {});
+    }
+
+    let stamp = VISA_COUNTS.may_load(deps.storage, &token_id)?.unwrap_or_default();
+    if contract.check_issuer(deps.as_ref(), &info, &token).is_err() {
+        // Other JumpRings only see the traveler as they pass through
+        contract.check_minter(deps.as_ref(), &info)?;
+        let last = match stamp.checked_sub(1) {
+            Some(last) => VISAS.may_load(deps.storage, (&token_id, last))?,
+            None => None,
+        };
+        let in_passing = match (&direction, last) {
+            (VisaDirection::Entry, Some(last)) => last.direction == VisaDirection::Exit,
+            (VisaDirection::Exit, Some(last)) => {
+                last.direction == VisaDirection::Entry && last.portal == info.sender
+            }
+            (_, None) => false,
+        };
+        if !in_passing {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
+    let visa = Visa {
+        portal: info.sender.clone(),
+        planet,
+        direction,
+        block_time: env.block.time,
+    };
+    VISAS.save(deps.storage, (&token_id, stamp), &visa)?;
+    VISA_COUNTS.save(deps.storage, &token_id, &(stamp + 1))?;
+
+    Ok(Response::new()
+        .add_attribute("action", "stamp_visa")
+        .add_attribute("portal", info.sender)
+        .add_attribute("token_id", token_id)
+        .add_attribute("stamp", stamp.to_string()))
+}
+
+fn update_image(
+    contract: &Cw721Contract<Extension, Empty, PassportExecuteMsg, PassportQueryMsg>,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    image: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut token = contract.tokens.load(deps.storage, &token_id)?;
+    contract.check_delegated(
+        deps.as_ref(),
+        &env,
+        &info,
+        &token,
+        DelegationScope::UpdateImage,
+    )?;
+    if token.revoked.is_some() {
+        return Err(ContractError::Revoked {});
+    }
+
+    let metadata = token.extension.get_or_insert_with(Metadata::default);
+    metadata.image = image;
+    contract.tokens.save(deps.storage, &token_id, &token)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_image")
+        .add_attribute("sender", info.sender)
+        .add_attribute("token_id", token_id))
+}
+
+fn reindex_passports(
+    contract: &Cw721Contract<Extension, Empty, PassportExecuteMsg, PassportQueryMsg>,
+    deps: DepsMut,
+    info: MessageInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    contract.check_admin(deps.as_ref(), &info)?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    let page = contract
+        .tokens
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Passports that don't know their issuing JumpRing yet learn it from their metadata
+    for (token_id, mut token) in page.iter().cloned() {
+        if token.issuer.is_none() {
+            token.issuer = token.extension.as_ref().and_then(|m| m.issuer.clone());
+        }
+        contract.tokens.save(deps.storage, &token_id, &token)?;
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "reindex_passports")
+        .add_attribute("reindexed", page.len().to_string())
+        .add_attribute("done", (page.len() < limit).to_string());
+    if let Some((last_token_id, _)) = page.last() {
+        res = res.add_attribute("last_token_id", last_token_id);
+    }
+    Ok(res)
+}
+
+pub fn identity_idx(d: &TokenInfo<Extension>) -> String {
+    d.extension
+        .as_ref()
+        .and_then(|m| m.identity.as_ref())
+        .map(Addr::to_string)
+        .unwrap_or_default()
+}
+
+pub fn species_idx(d: &TokenInfo<Extension>) -> String {
+    d.extension
+        .as_ref()
+        .and_then(|m| m.species.clone())
+        .unwrap_or_default()
+}
+
+pub fn issuer_idx(d: &TokenInfo<Extension>) -> String {
+    d.extension
+        .as_ref()
+        .and_then(|m| m.issuer.as_ref())
+        .map(Addr::to_string)
+        .unwrap_or_default()
+}
+
+fn visas(
+    deps: Deps,
+    token_id: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VisasResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let visas = VISAS
+        .prefix(&token_id)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(stamp, visa)| VisaEntry { stamp, visa }))
+        .collect::<StdResult<_>>()?;
+    Ok(VisasResponse { visas })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn passports_by(
+    contract: &Cw721Contract<Extension, Empty, PassportExecuteMsg, PassportQueryMsg>,
+    deps: Deps,
+    env: &Env,
+    index: &str,
+    value: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PassportInfo>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|s| Bound::ExclusiveRaw(s.into()));
+    contract
+        .tokens
+        .idx
+        .extension(index)
+        .ok_or_else(|| StdError::not_found(index))?
+        .prefix(value)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(token_id, token)| PassportInfo {
+                token_id,
+                owner: token.owner.clone(),
+                validity: token.validity(&env.block),
+                extension: token.extension,
+            })
+        })
+        .collect()
+}
@@ -1,15 +1,30 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use cosmwasm_std::{Empty, Addr, Reply, SubMsgResult};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use universe::migrate::check_upgrade;
 
-pub use cw721_soulbound::{ContractError, InstantiateMsg, MintMsg, MinterResponse, QueryMsg};
+pub use cw721_soulbound::{
+    ContractError, DelegationScope, DelegationsResponse, InstantiateMsg, MintMsg, MinterResponse,
+    MintersResponse, NftInfosResponse, QueryMsg,
+};
 pub use universe::species::{Species, SapienceScale};
 
 pub use cw721::{ContractInfoResponse};
 
+pub mod extension;
+pub use crate::extension::{
+    PassportExecuteMsg, PassportExtension, PassportInfo, PassportQueryMsg, PassportResponse,
+    PassportsResponse, Visa, VisaDirection, VisaEntry, VisasResponse,
+};
+
+pub mod recovery;
+pub use crate::recovery::{
+    PendingRecovery, RecoveryGuardians, RecoveryResponse, RECOVERY_TIMELOCK_SECONDS,
+};
+
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, JsonSchema, Debug)]
 pub struct Metadata {
     pub name: Option<String>,           // A human readable username (name is required for interoperability with NFT marketplaces)
     pub description: Option<String>,    // Description is also required for interoperability with NFT marketplaces
@@ -24,9 +39,60 @@ pub struct Metadata {
 
 pub type Extension = Option<Metadata>;
 
-pub type Cw721MetadataContract<'a> = cw721_soulbound::Cw721Contract<'a, Extension, Empty, Empty, Empty>;
+pub type Cw721MetadataContract<'a> = cw721_soulbound::Cw721Contract<'a, Extension, Empty, PassportExecuteMsg, PassportQueryMsg>;
+
+pub type ExecuteMsg = cw721_soulbound::ExecuteMsg<Extension, PassportExecuteMsg>;
+
+/// The passport contract, with passports indexed by identity, species and issuer
+pub fn passport_contract() -> Cw721MetadataContract<'static> {
+    Cw721MetadataContract::with_indexes(vec![
+        (extension::IDENTITY_INDEX, extension::identity_idx),
+        (extension::SPECIES_INDEX, extension::species_idx),
+        (extension::ISSUER_INDEX, extension::issuer_idx),
+    ])
+}
+
+/// Passport fields that can be corrected once minted. Every other field proves
+/// who a traveler is, or where and how they became a cyborg, and is fixed
+pub const MUTABLE_FIELDS: [&str; 5] = ["name", "description", "image", "species", "sapience_level"];
+
+pub fn check_immutable_fields(current: &Extension, updated: &Extension) -> Result<(), ContractError> {
+    let current = current.clone().unwrap_or_default();
+    let updated = updated.clone().unwrap_or_default();
+
+    // Metadata is destructured in full, so a new field has to be compared
+    // here before this compiles, and it stays fixed unless it's allowlisted
+    let Metadata {
+        name,
+        description,
+        image,
+        dna,
+        species,
+        sapience_level,
+        issuer,
+        origin,
+        identity,
+    } = current;
+    let fields = [
+        ("name", name != updated.name),
+        ("description", description != updated.description),
+        ("image", image != updated.image),
+        ("dna", dna != updated.dna),
+        ("species", species != updated.species),
+        ("sapience_level", sapience_level != updated.sapience_level),
+        ("issuer", issuer != updated.issuer),
+        ("origin", origin != updated.origin),
+        ("identity", identity != updated.identity),
+    ];
+    match fields.iter().find(|(field, changed)| *changed && !MUTABLE_FIELDS.contains(field)) {
+        Some((field, _)) => Err(ContractError::ImmutableField { field: field.to_string() }),
+        None => Ok(()),
+    }
+}
 
-pub type ExecuteMsg = cw721_soulbound::ExecuteMsg<Extension, Empty>;
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct MigrateMsg {}
 
 const CONTRACT_NAME: &str = "crates.io:passport-token";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -36,29 +102,20 @@ pub mod entry {
 
     #[cfg(not(feature = "library"))]
     use cosmwasm_std::entry_point;
-    use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{
+        Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    };
 
     #[cfg_attr(not(feature = "library"), entry_point)]
     pub fn instantiate(
-        deps: DepsMut,
-        _env: Env,
-        _info: MessageInfo,
+        mut deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
         msg: InstantiateMsg,
     ) -> StdResult<Response> {
+        let res = passport_contract().instantiate(deps.branch(), env, info, msg)?;
         set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-
-        let info = ContractInfoResponse {
-            name: msg.name,
-            symbol: msg.symbol,
-        };
-        Cw721MetadataContract::default()
-            .contract_info
-            .save(deps.storage, &info)?;
-        let minter = deps.api.addr_validate(&msg.minter)?;
-        Cw721MetadataContract::default()
-            .minter
-            .save(deps.storage, &minter)?;
-        Ok(Response::default())
+        Ok(res)
     }
 
     #[cfg_attr(not(feature = "library"), entry_point)]
@@ -68,7 +125,12 @@ pub mod entry {
         info: MessageInfo,
         msg: ExecuteMsg,
     ) -> Result<Response, ContractError> {
-        Cw721MetadataContract::default().execute(deps, env, info, msg)
+        let contract = passport_contract();
+        if let ExecuteMsg::UpdateMetadata { token_id, extension } = &msg {
+            let current = contract.tokens.load(deps.storage, token_id)?;
+            check_immutable_fields(&current.extension, extension)?;
+        }
+        contract.execute_with_extension(deps, env, info, msg, &PassportExtension)
     }
 
     #[cfg_attr(not(feature = "library"), entry_point)]
@@ -80,8 +142,28 @@ pub mod entry {
     }
 
     #[cfg_attr(not(feature = "library"), entry_point)]
-    pub fn query(deps: Deps, env: Env, msg: QueryMsg<Empty>) -> StdResult<Binary> {
-        Cw721MetadataContract::default().query(deps, env, msg)
+    pub fn query(deps: Deps, env: Env, msg: QueryMsg<PassportQueryMsg>) -> StdResult<Binary> {
+        passport_contract().query_with_extension(deps, env, msg, &PassportExtension)
+    }
+
+    #[cfg_attr(not(feature = "library"), entry_point)]
+    pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+        let stored = get_contract_version(deps.storage)?;
+        check_upgrade(&stored.contract, &stored.version, CONTRACT_NAME, CONTRACT_VERSION)
+            .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+        let contract = passport_contract();
+        contract.migrate_legacy_minter(deps.storage)?;
+
+        // Passports minted before the passport indexes existed aren't in them yet.
+        // There can be too many to save again here, so the admin files them
+        // a page at a time afterwards, with `PassportExecuteMsg::ReindexPassports`
+
+        set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+        Ok(Response::new()
+            .add_attribute("action", "migrate")
+            .add_attribute("from_version", stored.version)
+            .add_attribute("to_version", CONTRACT_VERSION))
     }
 }
 
@@ -89,12 +171,25 @@ pub mod entry {
 mod tests {
     use super::*;
 
+    use cosmwasm_std::{from_binary, Attribute, Deps, DepsMut};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cw721::{Cw721Query};
+    use cw_storage_plus::{Item, Map};
 
     const MINTER: &str = "jumpring";    // Each JumpRing mints passports and handles passport validation;
                                         // (Like airport security and an intergalactic embassy combined)
 
+    // Instantiates the passport contract, with `MINTER` as its admin and first minter
+    fn setup(deps: DepsMut) {
+        let init_msg = InstantiateMsg {
+            name: "passport token".to_string(),
+            symbol: "PASS".to_string(),
+            minter: MINTER.to_string(),
+            unique_owners: true,
+        };
+        entry::instantiate(deps, mock_env(), mock_info(MINTER, &[]), init_msg).unwrap();
+    }
+
     #[test]
     fn use_metadata_extension() {
         let mut deps = mock_dependencies();
@@ -105,6 +200,7 @@ mod tests {
             name: "passport token".to_string(),
             symbol: "PASS".to_string(),
             minter: MINTER.to_string(),
+            unique_owners: true,
         };
         contract
             .instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg)
@@ -133,15 +229,762 @@ mod tests {
             owner: MINTER.to_string(),
             token_uri: None,
             extension: metadata_extension,
+            expires: None,
+            notify: None,
         };
         let exec_msg = ExecuteMsg::Mint(mint_msg.clone());
         contract
             .execute(deps.as_mut(), mock_env(), info, exec_msg)
             .unwrap();
 
-        let res = contract.nft_info(deps.as_ref(), token_id.into()).unwrap();
+        let res = contract.nft_info(deps.as_ref(), mock_env(), token_id.into()).unwrap();
 
         assert_eq!(res.token_uri, mint_msg.token_uri);
         assert_eq!(res.extension, mint_msg.extension);
     }
+
+    #[test]
+    fn updating_metadata() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let info = mock_info(MINTER, &[]);
+
+        let metadata = Metadata {
+            name: Some("Traveler Name".into()),
+            description: None,
+            image: None,
+            dna: Some("v1-0309000402-4313".into()),
+            species: Some("Human".into()),
+            sapience_level: Some(SapienceScale::Medium),
+            issuer: Some(Addr::unchecked(MINTER)),
+            origin: Some("earth".into()),
+            identity: Some(Addr::unchecked("traveler")),
+        };
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "traveler".to_string(),
+            owner: "traveler".to_string(),
+            token_uri: None,
+            extension: Some(metadata.clone()),
+            expires: None,
+            notify: None,
+        });
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+
+        // Names, descriptions, images, species and sapience can be corrected
+        let corrected = Metadata {
+            name: Some("Corrected Name".into()),
+            description: Some("Corrected description".into()),
+            species: Some("Cyborg".into()),
+            sapience_level: Some(SapienceScale::High),
+            image: Some("ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".into()),
+            ..metadata
+        };
+        let update_msg = ExecuteMsg::UpdateMetadata {
+            token_id: "traveler".to_string(),
+            extension: Some(corrected.clone()),
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), update_msg).unwrap();
+        let res = Cw721MetadataContract::default()
+            .nft_info(deps.as_ref(), mock_env(), "traveler".into())
+            .unwrap();
+        assert_eq!(res.extension, Some(corrected.clone()));
+
+        // But proof of identity can't
+        let forged = [
+            ("dna", Metadata { dna: Some("v1-0000-0000".into()), ..corrected.clone() }),
+            ("issuer", Metadata { issuer: None, ..corrected.clone() }),
+            ("origin", Metadata { origin: Some("mars".into()), ..corrected.clone() }),
+            ("identity", Metadata { identity: Some(Addr::unchecked("impostor")), ..corrected }),
+        ];
+        for (field, metadata) in forged {
+            let update_msg = ExecuteMsg::UpdateMetadata {
+                token_id: "traveler".to_string(),
+                extension: Some(metadata),
+            };
+            let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), update_msg).unwrap_err();
+            assert_eq!(err, ContractError::ImmutableField { field: field.to_string() });
+        }
+        let update_msg = ExecuteMsg::UpdateMetadata {
+            token_id: "traveler".to_string(),
+            extension: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), info, update_msg).unwrap_err();
+    }
+
+    #[test]
+    fn stamping_visas() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let info = mock_info(MINTER, &[]);
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "traveler".to_string(),
+            owner: "traveler".to_string(),
+            token_uri: None,
+            extension: None,
+            expires: None,
+            notify: None,
+        });
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+
+        // Only JumpRings that can mint passports can stamp them
+        let stamp_msg = |planet: &str, direction| ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::StampVisa {
+                token_id: "traveler".to_string(),
+                planet: planet.to_string(),
+                direction,
+            },
+        };
+        let err = entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("traveler", &[]),
+            stamp_msg("earth", VisaDirection::Exit),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let stamps = [("earth", VisaDirection::Exit), ("jupiter", VisaDirection::Entry)];
+        for (planet, direction) in stamps {
+            let msg = stamp_msg(planet, direction);
+            entry::execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        }
+
+        // Visas are listed oldest first, a page at a time
+        let visas_query = |start_after| QueryMsg::Extension {
+            msg: PassportQueryMsg::Visas {
+                token_id: "traveler".to_string(),
+                start_after,
+                limit: Some(1),
+            },
+        };
+        let res = entry::query(deps.as_ref(), mock_env(), visas_query(None)).unwrap();
+        let page: VisasResponse = from_binary(&res).unwrap();
+        assert_eq!(page.visas.len(), 1);
+        assert_eq!(page.visas[0].visa.direction, VisaDirection::Exit);
+        let res = entry::query(deps.as_ref(), mock_env(), visas_query(Some(0))).unwrap();
+        let page: VisasResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            page.visas,
+            vec![VisaEntry {
+                stamp: 1,
+                visa: Visa {
+                    portal: Addr::unchecked(MINTER),
+                    planet: "jupiter".to_string(),
+                    direction: VisaDirection::Entry,
+                    block_time: mock_env().block.time,
+                },
+            }]
+        );
+
+        // Revoked passports can't be stamped
+        let revoke_msg = ExecuteMsg::RevokeToken {
+            token_id: "traveler".to_string(),
+            reason: "Overstayed".to_string(),
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), revoke_msg).unwrap();
+        let err = entry::execute(deps.as_mut(), mock_env(), info, stamp_msg("jupiter", VisaDirection::Exit)).unwrap_err();
+        assert_eq!(err, ContractError::Revoked {});
+    }
+
+    #[test]
+    fn altering_passports_of_other_jumprings() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let info = mock_info(MINTER, &[]);
+        let add_msg = ExecuteMsg::AddMinter { minter: "mars".to_string(), quota: None };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), add_msg).unwrap();
+        let add_msg = ExecuteMsg::AddMinter { minter: "venus".to_string(), quota: None };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), add_msg).unwrap();
+
+        let metadata = Metadata {
+            sapience_level: Some(SapienceScale::Low),
+            issuer: Some(Addr::unchecked(MINTER)),
+            identity: Some(Addr::unchecked("traveler")),
+            ..Metadata::default()
+        };
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "traveler".to_string(),
+            owner: "traveler".to_string(),
+            token_uri: None,
+            extension: Some(metadata.clone()),
+            expires: None,
+            notify: None,
+        });
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+
+        // Another JumpRing can't rewrite, revoke or stamp a passport it didn't issue
+        let mars = mock_info("mars", &[]);
+        let stamp_msg = |planet: &str, direction| ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::StampVisa {
+                token_id: "traveler".to_string(),
+                planet: planet.to_string(),
+                direction,
+            },
+        };
+        let forbidden = [
+            ExecuteMsg::UpdateMetadata {
+                token_id: "traveler".to_string(),
+                extension: Some(Metadata { sapience_level: Some(SapienceScale::High), ..metadata }),
+            },
+            ExecuteMsg::RevokeToken { token_id: "traveler".to_string(), reason: "Alien".to_string() },
+            stamp_msg("mars", VisaDirection::Entry),
+            stamp_msg("mars", VisaDirection::Exit),
+        ];
+        for msg in forbidden {
+            let err = entry::execute(deps.as_mut(), mock_env(), mars.clone(), msg).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+        }
+
+        // But it can stamp the traveler in once they've left, and out again
+        entry::execute(deps.as_mut(), mock_env(), info, stamp_msg("earth", VisaDirection::Exit)).unwrap();
+        entry::execute(deps.as_mut(), mock_env(), mars.clone(), stamp_msg("mars", VisaDirection::Entry)).unwrap();
+        let venus = mock_info("venus", &[]);
+        for direction in [VisaDirection::Entry, VisaDirection::Exit] {
+            let err = entry::execute(deps.as_mut(), mock_env(), venus.clone(), stamp_msg("venus", direction)).unwrap_err();
+            assert_eq!(err, ContractError::Unauthorized {});
+        }
+        entry::execute(deps.as_mut(), mock_env(), mars, stamp_msg("mars", VisaDirection::Exit)).unwrap();
+        entry::execute(deps.as_mut(), mock_env(), venus, stamp_msg("venus", VisaDirection::Entry)).unwrap();
+    }
+
+    #[test]
+    fn updating_images() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let info = mock_info(MINTER, &[]);
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "traveler".to_string(),
+            owner: "traveler".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                name: Some("Traveler Name".into()),
+                dna: Some("Example DNA String".into()),
+                ..Metadata::default()
+            }),
+            expires: None,
+            notify: None,
+        });
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+
+        let update_msg = |image: &str| ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::UpdateImage {
+                token_id: "traveler".to_string(),
+                image: Some(image.to_string()),
+            },
+        };
+        let image = |deps: Deps| {
+            let passport = passport_contract()
+                .nft_info(deps, mock_env(), "traveler".to_string())
+                .unwrap();
+            passport.extension.unwrap().image
+        };
+
+        // Minters can't update images, only travelers
+        let err = entry::execute(deps.as_mut(), mock_env(), info, update_msg("ipfs://minter"))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let traveler = mock_info("traveler", &[]);
+        entry::execute(deps.as_mut(), mock_env(), traveler.clone(), update_msg("ipfs://selfie"))
+            .unwrap();
+        assert_eq!(image(deps.as_ref()), Some("ipfs://selfie".to_string()));
+
+        // or whoever they've trusted with their image
+        let photographer = mock_info("photographer", &[]);
+        let err = entry::execute(
+            deps.as_mut(),
+            mock_env(),
+            photographer.clone(),
+            update_msg("ipfs://portrait"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let delegate_msg = ExecuteMsg::Delegate {
+            token_id: "traveler".to_string(),
+            delegate: "photographer".to_string(),
+            scope: DelegationScope::UpdateImage,
+            expires: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), traveler, delegate_msg).unwrap();
+        entry::execute(deps.as_mut(), mock_env(), photographer, update_msg("ipfs://portrait"))
+            .unwrap();
+        assert_eq!(image(deps.as_ref()), Some("ipfs://portrait".to_string()));
+
+        // Nothing else about the passport changes
+        let passport = passport_contract()
+            .nft_info(deps.as_ref(), mock_env(), "traveler".to_string())
+            .unwrap()
+            .extension
+            .unwrap();
+        assert_eq!(passport.name, Some("Traveler Name".into()));
+        assert_eq!(passport.dna, Some("Example DNA String".into()));
+    }
+
+    #[test]
+    fn recovering_passports() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let info = mock_info(MINTER, &[]);
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "lost".to_string(),
+            owner: "lost".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                issuer: Some(Addr::unchecked(MINTER)),
+                identity: Some(Addr::unchecked("lost")),
+                ..Metadata::default()
+            }),
+            expires: None,
+            notify: None,
+        });
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+        let stamp_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::StampVisa {
+                token_id: "lost".to_string(),
+                planet: "earth".to_string(),
+                direction: VisaDirection::Exit,
+            },
+        };
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), stamp_msg).unwrap();
+
+        // Travelers choose their own guardians, and how many must agree
+        let owner = mock_info("lost", &[]);
+        let guardians_msg = |threshold| ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::SetRecoveryGuardians {
+                token_id: "lost".to_string(),
+                guardians: vec!["mom".to_string(), "dad".to_string(), "dog".to_string()],
+                threshold,
+            },
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), guardians_msg(2))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = entry::execute(deps.as_mut(), mock_env(), owner.clone(), guardians_msg(4))
+            .unwrap_err();
+        assert_eq!(err, ContractError::InvalidRecoveryThreshold { threshold: 4, guardians: 3 });
+        entry::execute(deps.as_mut(), mock_env(), owner, guardians_msg(2)).unwrap();
+
+        // One guardian isn't enough
+        let approve_msg = |new_identity: &str| ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::ApproveRecovery {
+                token_id: "lost".to_string(),
+                new_identity: new_identity.to_string(),
+            },
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), approve_msg("found"))
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        entry::execute(deps.as_mut(), mock_env(), mock_info("mom", &[]), approve_msg("found"))
+            .unwrap();
+        let err = entry::execute(deps.as_mut(), mock_env(), mock_info("dad", &[]), approve_msg("thief"))
+            .unwrap_err();
+        assert_eq!(err, ContractError::RecoveryMismatch { pending: "found".to_string() });
+        let recovery_query = |token_id: &str| QueryMsg::Extension {
+            msg: PassportQueryMsg::Recovery { token_id: token_id.to_string() },
+        };
+        let res = entry::query(deps.as_ref(), mock_env(), recovery_query("lost")).unwrap();
+        let res: RecoveryResponse = from_binary(&res).unwrap();
+        assert_eq!(
+            res.pending,
+            Some(PendingRecovery {
+                new_identity: Addr::unchecked("found"),
+                approvals: vec![Addr::unchecked("mom")],
+                unlocks_at: None,
+            })
+        );
+
+        // Two guardians move the passport, its visas and its guardians to the new identity
+        let res = entry::execute(deps.as_mut(), mock_env(), mock_info("dad", &[]), approve_msg("found"))
+            .unwrap();
+        let event = &res.events[0];
+        assert_eq!(event.ty, "passport_recovered");
+        assert!(event.attributes.contains(&Attribute::new("method", "guardians")));
+        assert!(event.attributes.contains(&Attribute::new("approvals", "mom,dad")));
+        let contract = passport_contract();
+        assert!(!contract.tokens.has(&deps.storage, "lost"));
+        let passport = contract.tokens.load(&deps.storage, "found").unwrap();
+        assert_eq!(passport.owner, Addr::unchecked("found"));
+        assert_eq!(passport.extension.unwrap().identity, Some(Addr::unchecked("found")));
+        let identity_query = QueryMsg::Extension {
+            msg: PassportQueryMsg::PassportByIdentity { address: "found".to_string() },
+        };
+        let res = entry::query(deps.as_ref(), mock_env(), identity_query).unwrap();
+        let res: PassportResponse = from_binary(&res).unwrap();
+        assert_eq!(res.passport.unwrap().token_id, "found");
+        let tokens = contract.tokens(deps.as_ref(), "lost".to_string(), None, None).unwrap();
+        assert!(tokens.tokens.is_empty());
+        let visas_query = QueryMsg::Extension {
+            msg: PassportQueryMsg::Visas {
+                token_id: "found".to_string(),
+                start_after: None,
+                limit: None,
+            },
+        };
+        let res = entry::query(deps.as_ref(), mock_env(), visas_query).unwrap();
+        let res: VisasResponse = from_binary(&res).unwrap();
+        assert_eq!(res.visas.len(), 1);
+        let res = entry::query(deps.as_ref(), mock_env(), recovery_query("found")).unwrap();
+        let res: RecoveryResponse = from_binary(&res).unwrap();
+        assert_eq!(res.guardians.unwrap().threshold, 2);
+        assert_eq!(res.pending, None);
+
+        // The issuing JumpRing can also recover passports, but only after a timelock
+        let request_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::RequestRecovery {
+                token_id: "found".to_string(),
+                new_identity: "refound".to_string(),
+            },
+        };
+        let complete_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::CompleteRecovery {
+                token_id: "found".to_string(),
+                new_identity: "refound".to_string(),
+            },
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), mock_info("mom", &[]), request_msg.clone())
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), request_msg.clone()).unwrap();
+        let err = entry::execute(deps.as_mut(), mock_env(), info.clone(), complete_msg.clone())
+            .unwrap_err();
+        let unlocks_at = mock_env().block.time.plus_seconds(RECOVERY_TIMELOCK_SECONDS);
+        assert_eq!(err, ContractError::RecoveryTimelocked { unlocks_at: unlocks_at.to_string() });
+
+        // which gives travelers who still have their keys time to cancel it
+        let cancel_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::CancelRecovery { token_id: "found".to_string() },
+        };
+        entry::execute(deps.as_mut(), mock_env(), mock_info("found", &[]), cancel_msg).unwrap();
+        let mut later = mock_env();
+        later.block.time = unlocks_at;
+        let err = entry::execute(deps.as_mut(), later.clone(), info.clone(), complete_msg.clone())
+            .unwrap_err();
+        assert_eq!(err, ContractError::NoRecoveryPending {});
+
+        entry::execute(deps.as_mut(), mock_env(), info.clone(), request_msg).unwrap();
+        let res = entry::execute(deps.as_mut(), later, info, complete_msg).unwrap();
+        assert!(res.events[0].attributes.contains(&Attribute::new("method", "timelock")));
+        let passport = contract.tokens.load(&deps.storage, "refound").unwrap();
+        assert_eq!(passport.owner, Addr::unchecked("refound"));
+
+        // Travelers' delegates can ask for a recovery, but can't complete it,
+        // and travelers can still cancel it
+        let delegate_msg = ExecuteMsg::Delegate {
+            token_id: "refound".to_string(),
+            delegate: "helper".to_string(),
+            scope: DelegationScope::Recover,
+            expires: None,
+        };
+        entry::execute(deps.as_mut(), mock_env(), mock_info("refound", &[]), delegate_msg).unwrap();
+        let request_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::RequestRecovery {
+                token_id: "refound".to_string(),
+                new_identity: "helper".to_string(),
+            },
+        };
+        let complete_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::CompleteRecovery {
+                token_id: "refound".to_string(),
+                new_identity: "helper".to_string(),
+            },
+        };
+        let helper = mock_info("helper", &[]);
+        entry::execute(deps.as_mut(), mock_env(), helper.clone(), request_msg).unwrap();
+        let mut later = mock_env();
+        later.block.time = unlocks_at;
+        let err = entry::execute(deps.as_mut(), later.clone(), helper, complete_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = entry::execute(deps.as_mut(), later.clone(), mock_info("refound", &[]), complete_msg.clone())
+            .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let cancel_msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::CancelRecovery { token_id: "refound".to_string() },
+        };
+        entry::execute(deps.as_mut(), mock_env(), mock_info("refound", &[]), cancel_msg).unwrap();
+        let err = entry::execute(deps.as_mut(), later, mock_info(MINTER, &[]), complete_msg).unwrap_err();
+        assert_eq!(err, ContractError::NoRecoveryPending {});
+        assert!(contract.tokens.has(&deps.storage, "refound"));
+    }
+
+    #[test]
+    fn querying_passports() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+        let info = mock_info(MINTER, &[]);
+
+        let travelers = [
+            ("alice", "Cyborg", MINTER),
+            ("bob", "Human", MINTER),
+            ("carol", "Cyborg", "otherring"),
+        ];
+        for (traveler, species, issuer) in travelers {
+            let mint_msg = ExecuteMsg::Mint(MintMsg {
+                token_id: traveler.to_string(),
+                owner: traveler.to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    name: Some(traveler.to_string()),
+                    description: None,
+                    image: None,
+                    dna: None,
+                    species: Some(species.to_string()),
+                    sapience_level: Some(SapienceScale::High),
+                    issuer: Some(Addr::unchecked(issuer)),
+                    origin: None,
+                    identity: Some(Addr::unchecked(traveler)),
+                }),
+                expires: None,
+                notify: None,
+            });
+            entry::execute(deps.as_mut(), mock_env(), info.clone(), mint_msg).unwrap();
+        }
+
+        fn passports_query(deps: Deps, msg: PassportQueryMsg) -> Vec<String> {
+            let msg = QueryMsg::Extension { msg };
+            let res = entry::query(deps, mock_env(), msg).unwrap();
+            let res: PassportsResponse = from_binary(&res).unwrap();
+            res.passports.into_iter().map(|p| p.token_id).collect()
+        }
+        let cyborgs = passports_query(deps.as_ref(), PassportQueryMsg::PassportsBySpecies {
+            species: "Cyborg".to_string(),
+            start_after: None,
+            limit: None,
+        });
+        assert_eq!(cyborgs, vec!["alice".to_string(), "carol".to_string()]);
+        let cyborgs = passports_query(deps.as_ref(), PassportQueryMsg::PassportsBySpecies {
+            species: "Cyborg".to_string(),
+            start_after: Some("alice".to_string()),
+            limit: None,
+        });
+        assert_eq!(cyborgs, vec!["carol".to_string()]);
+        let issued = passports_query(deps.as_ref(), PassportQueryMsg::PassportsByIssuer {
+            issuer: MINTER.to_string(),
+            start_after: None,
+            limit: None,
+        });
+        assert_eq!(issued, vec!["alice".to_string(), "bob".to_string()]);
+
+        let identity_query = |address: &str| QueryMsg::Extension {
+            msg: PassportQueryMsg::PassportByIdentity { address: address.to_string() },
+        };
+        let res = entry::query(deps.as_ref(), mock_env(), identity_query("bob")).unwrap();
+        let res: PassportResponse = from_binary(&res).unwrap();
+        let passport = res.passport.unwrap();
+        assert_eq!(passport.token_id, "bob");
+        assert_eq!(passport.owner, Addr::unchecked("bob"));
+        let res = entry::query(deps.as_ref(), mock_env(), identity_query("dave")).unwrap();
+        let res: PassportResponse = from_binary(&res).unwrap();
+        assert_eq!(res.passport, None);
+
+        // Passports minted before the indexes existed are indexed after migrating
+        let unindexed = Cw721MetadataContract::default();
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "dave".to_string(),
+            owner: "dave".to_string(),
+            token_uri: None,
+            extension: Some(Metadata {
+                name: Some("dave".to_string()),
+                description: None,
+                image: None,
+                dna: None,
+                species: Some("Human".to_string()),
+                sapience_level: Some(SapienceScale::Medium),
+                issuer: Some(Addr::unchecked(MINTER)),
+                origin: None,
+                identity: Some(Addr::unchecked("dave")),
+            }),
+            expires: None,
+            notify: None,
+        });
+        unindexed.execute(deps.as_mut(), mock_env(), info, mint_msg).unwrap();
+        let res = entry::query(deps.as_ref(), mock_env(), identity_query("dave")).unwrap();
+        let res: PassportResponse = from_binary(&res).unwrap();
+        assert_eq!(res.passport, None);
+
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+        entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        reindex(deps.as_mut(), None, None);
+        let res = entry::query(deps.as_ref(), mock_env(), identity_query("dave")).unwrap();
+        let res: PassportResponse = from_binary(&res).unwrap();
+        assert_eq!(res.passport.unwrap().token_id, "dave");
+        let humans = passports_query(deps.as_ref(), PassportQueryMsg::PassportsBySpecies {
+            species: "Human".to_string(),
+            start_after: None,
+            limit: None,
+        });
+        assert_eq!(humans, vec!["bob".to_string(), "dave".to_string()]);
+    }
+
+    // Files a page of passports under the passport indexes, as the admin
+    fn reindex(deps: DepsMut, start_after: Option<String>, limit: Option<u32>) -> Vec<Attribute> {
+        let msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::ReindexPassports { start_after, limit },
+        };
+        entry::execute(deps, mock_env(), mock_info(MINTER, &[]), msg).unwrap().attributes
+    }
+
+    #[test]
+    fn reindexing_passports_in_pages() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        // Passports minted before the indexes existed
+        let unindexed = Cw721MetadataContract::default();
+        let travelers = ["alice", "bob", "carol", "dave", "erin"];
+        for traveler in travelers {
+            let mint_msg = ExecuteMsg::Mint(MintMsg {
+                token_id: traveler.to_string(),
+                owner: traveler.to_string(),
+                token_uri: None,
+                extension: Some(Metadata {
+                    species: Some("Cyborg".to_string()),
+                    issuer: Some(Addr::unchecked(MINTER)),
+                    identity: Some(Addr::unchecked(traveler)),
+                    ..Metadata::default()
+                }),
+                expires: None,
+                notify: None,
+            });
+            unindexed.execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg).unwrap();
+        }
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+        entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let cyborgs = |deps: Deps| {
+            let msg = QueryMsg::Extension {
+                msg: PassportQueryMsg::PassportsBySpecies {
+                    species: "Cyborg".to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            };
+            let res: PassportsResponse = from_binary(&entry::query(deps, mock_env(), msg).unwrap()).unwrap();
+            res.passports.into_iter().map(|p| p.token_id).collect::<Vec<_>>()
+        };
+        assert!(cyborgs(deps.as_ref()).is_empty());
+
+        // Only the admin can reindex
+        let msg = ExecuteMsg::Extension {
+            msg: PassportExecuteMsg::ReindexPassports { start_after: None, limit: None },
+        };
+        let err = entry::execute(deps.as_mut(), mock_env(), mock_info("traveler", &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Each page picks up where the last one left off
+        let attribute = |attributes: &[Attribute], key: &str| {
+            attributes.iter().find(|a| a.key == key).map(|a| a.value.clone())
+        };
+        let attributes = reindex(deps.as_mut(), None, Some(2));
+        assert_eq!(attribute(&attributes, "last_token_id"), Some("bob".to_string()));
+        assert_eq!(attribute(&attributes, "done"), Some("false".to_string()));
+        assert_eq!(cyborgs(deps.as_ref()), vec!["alice".to_string(), "bob".to_string()]);
+
+        let mut start_after = attribute(&attributes, "last_token_id");
+        let mut pages = 1;
+        loop {
+            let attributes = reindex(deps.as_mut(), start_after, Some(2));
+            pages += 1;
+            if attribute(&attributes, "done") == Some("true".to_string()) {
+                break;
+            }
+            start_after = attribute(&attributes, "last_token_id");
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(cyborgs(deps.as_ref()), travelers.map(String::from).to_vec());
+    }
+
+    #[test]
+    fn migrate_checks_version() {
+        let mut deps = mock_dependencies();
+        setup(deps.as_mut());
+
+        // Upgrading from an older version works
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+        entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        let version = get_contract_version(&deps.storage).unwrap();
+        assert_eq!(version.version, CONTRACT_VERSION.to_string());
+
+        // Downgrading, or migrating some other contract, doesn't
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+        entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        set_contract_version(&mut deps.storage, "crates.io:portal", "0.0.1").unwrap();
+        entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+    }
+
+    // Passports as stored before minters, delegations, revocation and expiry
+    #[derive(Serialize, Deserialize)]
+    struct LegacyTokenInfo {
+        owner: Addr,
+        approvals: Vec<Empty>,
+        token_uri: Option<String>,
+        extension: Extension,
+    }
+
+    #[test]
+    fn migrate_upgrades_single_minter() {
+        let mut deps = mock_dependencies();
+        let contract = passport_contract();
+
+        // Storage as the first passport contract left it, with its only minter under "minter"
+        let contract_info = ContractInfoResponse {
+            name: "passport token".to_string(),
+            symbol: "PASS".to_string(),
+        };
+        contract.contract_info.save(&mut deps.storage, &contract_info).unwrap();
+        Item::<Addr>::new("minter").save(&mut deps.storage, &Addr::unchecked(MINTER)).unwrap();
+        Item::<u64>::new("num_tokens").save(&mut deps.storage, &1).unwrap();
+        let legacy_token = LegacyTokenInfo {
+            owner: Addr::unchecked("alice"),
+            approvals: vec![],
+            token_uri: None,
+            extension: Some(Metadata {
+                species: Some("Cyborg".to_string()),
+                issuer: Some(Addr::unchecked(MINTER)),
+                identity: Some(Addr::unchecked("alice")),
+                ..Metadata::default()
+            }),
+        };
+        Map::<&str, LegacyTokenInfo>::new("tokens")
+            .save(&mut deps.storage, "alice", &legacy_token)
+            .unwrap();
+        set_contract_version(&mut deps.storage, CONTRACT_NAME, "0.0.1").unwrap();
+
+        entry::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(Item::<Addr>::new("minter").may_load(&deps.storage).unwrap().is_none());
+        reindex(deps.as_mut(), None, None);
+
+        // The old minter is now the admin, and can still mint without a quota
+        let minters = contract.minters(deps.as_ref(), None, None).unwrap();
+        assert_eq!(minters.minters.len(), 1);
+        assert_eq!(minters.minters[0].minter, MINTER.to_string());
+        assert_eq!(minters.minters[0].quota, None);
+        assert_eq!(minters.minters[0].minted, 1);
+        let add_msg = ExecuteMsg::AddMinter {
+            minter: "other_jumpring".to_string(),
+            quota: Some(1),
+        };
+        entry::execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), add_msg).unwrap();
+        let mint_msg = ExecuteMsg::Mint(MintMsg {
+            token_id: "bob".to_string(),
+            owner: "bob".to_string(),
+            token_uri: None,
+            extension: None,
+            expires: None,
+            notify: None,
+        });
+        entry::execute(deps.as_mut(), mock_env(), mock_info(MINTER, &[]), mint_msg).unwrap();
+        let count = contract.num_tokens(deps.as_ref()).unwrap();
+        assert_eq!(count.count, 2);
+        let minter = contract.minter(deps.as_ref()).unwrap();
+        assert_eq!(minter.minter, MINTER.to_string());
+
+        // Passports minted before the migration are readable, and indexed
+        let passport = contract.nft_info(deps.as_ref(), mock_env(), "alice".to_string()).unwrap();
+        assert_eq!(passport.extension.unwrap().species, Some("Cyborg".to_string()));
+        let tokens = contract.tokens(deps.as_ref(), "alice".to_string(), None, None).unwrap();
+        assert_eq!(tokens.tokens, vec!["alice".to_string()]);
+
+        // And know their issuing JumpRing
+        let token = contract.tokens.load(&deps.storage, "alice").unwrap();
+        assert_eq!(token.issuer, Some(Addr::unchecked(MINTER)));
+    }
 }
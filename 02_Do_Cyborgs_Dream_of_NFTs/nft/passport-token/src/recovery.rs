@@ -0,0 +1,316 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::{
+    Addr, Deps, DepsMut, Env, Event, MessageInfo, Order, Response, StdResult, Storage, Timestamp,
+};
+use cw_storage_plus::Map;
+use cw721_soulbound::state::TokenInfo;
+use cw721_soulbound::{ContractError, DelegationScope};
+
+use crate::extension::{VISAS, VISA_COUNTS};
+use crate::{Cw721MetadataContract, Extension};
+
+// How long a requested recovery must wait before it can be completed,
+// giving a traveler who still has their keys time to cancel it
+pub const RECOVERY_TIMELOCK_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RecoveryGuardians {
+    pub guardians: Vec<Addr>,
+    pub threshold: u32,             // Guardian approvals needed to recover the passport
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PendingRecovery {
+    pub new_identity: Addr,
+    pub approvals: Vec<Addr>,           // Guardians who've approved the recovery so far
+    pub unlocks_at: Option<Timestamp>,  // When the issuer can complete it, once it's been requested
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct RecoveryResponse {
+    pub guardians: Option<RecoveryGuardians>,
+    pub pending: Option<PendingRecovery>,
+}
+
+// Guardians each traveler has chosen to vouch for them, keyed by `token_id`
+pub const RECOVERY_GUARDIANS: Map<&str, RecoveryGuardians> = Map::new("recovery_guardians");
+
+// At most one recovery can be pending for each passport
+pub const PENDING_RECOVERIES: Map<&str, PendingRecovery> = Map::new("pending_recoveries");
+
+pub fn set_recovery_guardians(
+    contract: &Cw721MetadataContract,
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+    guardians: Vec<String>,
+    threshold: u32,
+) -> Result<Response, ContractError> {
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut guardians = guardians
+        .iter()
+        .map(|g| deps.api.addr_validate(g))
+        .collect::<StdResult<Vec<_>>>()?;
+    guardians.sort();
+    guardians.dedup();
+    if threshold == 0 || threshold as usize > guardians.len() {
+        return Err(ContractError::InvalidRecoveryThreshold {
+            threshold,
+            guardians: guardians.len() as u32,
+        });
+    }
+
+    // Approvals from the old guardians don't count for the new ones
+    PENDING_RECOVERIES.remove(deps.storage, &token_id);
+    RECOVERY_GUARDIANS.save(deps.storage, &token_id, &RecoveryGuardians { guardians, threshold })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_recovery_guardians")
+        .add_attribute("token_id", token_id)
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+/// A guardian approves moving the passport to `new_identity`. The
+/// passport is recovered as soon as enough guardians have approved
+pub fn approve_recovery(
+    contract: &Cw721MetadataContract,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    new_identity: String,
+) -> Result<Response, ContractError> {
+    let guardians = RECOVERY_GUARDIANS
+        .may_load(deps.storage, &token_id)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if !guardians.guardians.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_identity = deps.api.addr_validate(&new_identity)?;
+    let mut pending = load_pending(deps.storage, &token_id, &new_identity)?;
+    if !pending.approvals.contains(&info.sender) {
+        pending.approvals.push(info.sender.clone());
+    }
+
+    if pending.approvals.len() >= guardians.threshold as usize {
+        let res = recover(contract, deps, env, &token_id, &pending, "guardians")?;
+        return Ok(res.add_attribute("approver", info.sender));
+    }
+
+    PENDING_RECOVERIES.save(deps.storage, &token_id, &pending)?;
+    Ok(Response::new()
+        .add_attribute("action", "approve_recovery")
+        .add_attribute("token_id", token_id)
+        .add_attribute("new_identity", new_identity)
+        .add_attribute("approver", info.sender)
+        .add_attribute("approvals", pending.approvals.len().to_string()))
+}
+
+/// The issuing JumpRing, the traveler, or anyone they've delegated `recover`
+/// to, starts the timelock on moving the passport to `new_identity`. Only
+/// the issuer can complete it, and the traveler can cancel it until then
+pub fn request_recovery(
+    contract: &Cw721MetadataContract,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    new_identity: String,
+) -> Result<Response, ContractError> {
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    if !is_issuer(contract, deps.as_ref(), &info, &token) {
+        contract.check_delegated(deps.as_ref(), &env, &info, &token, DelegationScope::Recover)?;
+    }
+
+    let new_identity = deps.api.addr_validate(&new_identity)?;
+    let mut pending = load_pending(deps.storage, &token_id, &new_identity)?;
+    let unlocks_at = env.block.time.plus_seconds(RECOVERY_TIMELOCK_SECONDS);
+    pending.unlocks_at = Some(unlocks_at);
+    PENDING_RECOVERIES.save(deps.storage, &token_id, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "request_recovery")
+        .add_attribute("token_id", token_id)
+        .add_attribute("new_identity", new_identity)
+        .add_attribute("requested_by", info.sender)
+        .add_attribute("unlocks_at", unlocks_at.to_string()))
+}
+
+/// The issuing JumpRing moves the passport once its recovery timelock has
+/// passed. Neither the traveler nor their delegates can, so a `recover`
+/// delegation can't be used to take over a passport
+pub fn complete_recovery(
+    contract: &Cw721MetadataContract,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+    new_identity: String,
+) -> Result<Response, ContractError> {
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    if !is_issuer(contract, deps.as_ref(), &info, &token) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let new_identity = deps.api.addr_validate(&new_identity)?;
+    let pending = PENDING_RECOVERIES
+        .may_load(deps.storage, &token_id)?
+        .ok_or(ContractError::NoRecoveryPending {})?;
+    if pending.new_identity != new_identity {
+        return Err(ContractError::RecoveryMismatch {
+            pending: pending.new_identity.into(),
+        });
+    }
+    match pending.unlocks_at {
+        Some(unlocks_at) if unlocks_at <= env.block.time => {}
+        Some(unlocks_at) => {
+            return Err(ContractError::RecoveryTimelocked {
+                unlocks_at: unlocks_at.to_string(),
+            })
+        }
+        None => return Err(ContractError::NoRecoveryPending {}),
+    }
+
+    let res = recover(contract, deps, env, &token_id, &pending, "timelock")?;
+    Ok(res.add_attribute("completed_by", info.sender))
+}
+
+/// Travelers who still have their keys can stop a recovery they didn't ask for
+pub fn cancel_recovery(
+    contract: &Cw721MetadataContract,
+    deps: DepsMut,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let token = contract.tokens.load(deps.storage, &token_id)?;
+    if token.owner != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let pending = PENDING_RECOVERIES
+        .may_load(deps.storage, &token_id)?
+        .ok_or(ContractError::NoRecoveryPending {})?;
+    PENDING_RECOVERIES.remove(deps.storage, &token_id);
+
+    let event = Event::new("passport_recovery_cancelled")
+        .add_attribute("token_id", &token_id)
+        .add_attribute("new_identity", pending.new_identity);
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "cancel_recovery")
+        .add_attribute("token_id", token_id))
+}
+
+pub fn recovery(deps: Deps, token_id: String) -> StdResult<RecoveryResponse> {
+    Ok(RecoveryResponse {
+        guardians: RECOVERY_GUARDIANS.may_load(deps.storage, &token_id)?,
+        pending: PENDING_RECOVERIES.may_load(deps.storage, &token_id)?,
+    })
+}
+
+// Passports are keyed by their traveler's identity, so recovering one
+// re-keys it, along with its visas and guardians, under the new identity
+fn recover(
+    contract: &Cw721MetadataContract,
+    deps: DepsMut,
+    env: Env,
+    token_id: &str,
+    pending: &PendingRecovery,
+    method: &str,
+) -> Result<Response, ContractError> {
+    let new_identity = &pending.new_identity;
+    let mut token = contract.tokens.load(deps.storage, token_id)?;
+    if token.revoked.is_some() {
+        return Err(ContractError::Revoked {});
+    }
+    if contract.tokens.has(deps.storage, new_identity.as_str())
+        || contract.tokens.idx.unique_owner.owns_token(deps.storage, new_identity)?
+    {
+        return Err(ContractError::OwnerHasToken {
+            owner: new_identity.to_string(),
+        });
+    }
+
+    // Whoever the old identity delegated to has no say over the new one
+    let old_owner = token.owner.clone();
+    token.owner = new_identity.clone();
+    token.approvals.clear();
+    if let Some(metadata) = token.extension.as_mut() {
+        metadata.identity = Some(new_identity.clone());
+    }
+    contract.tokens.remove(deps.storage, token_id)?;
+    contract.tokens.save(deps.storage, new_identity.as_str(), &token)?;
+
+    let stamps = VISAS
+        .prefix(token_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (stamp, visa) in stamps {
+        VISAS.remove(deps.storage, (token_id, stamp));
+        VISAS.save(deps.storage, (new_identity.as_str(), stamp), &visa)?;
+    }
+    if let Some(count) = VISA_COUNTS.may_load(deps.storage, token_id)? {
+        VISA_COUNTS.remove(deps.storage, token_id);
+        VISA_COUNTS.save(deps.storage, new_identity.as_str(), &count)?;
+    }
+    if let Some(guardians) = RECOVERY_GUARDIANS.may_load(deps.storage, token_id)? {
+        RECOVERY_GUARDIANS.remove(deps.storage, token_id);
+        RECOVERY_GUARDIANS.save(deps.storage, new_identity.as_str(), &guardians)?;
+    }
+    PENDING_RECOVERIES.remove(deps.storage, token_id);
+
+    let approvals: Vec<_> = pending.approvals.iter().map(Addr::to_string).collect();
+    let event = Event::new("passport_recovered")
+        .add_attribute("old_token_id", token_id)
+        .add_attribute("new_token_id", new_identity)
+        .add_attribute("old_owner", old_owner)
+        .add_attribute("new_owner", new_identity)
+        .add_attribute("method", method)
+        .add_attribute("approvals", approvals.join(","))
+        .add_attribute("height", env.block.height.to_string());
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "recover_passport")
+        .add_attribute("token_id", new_identity))
+}
+
+// A recovery already pending for another identity has to be cancelled first
+fn load_pending(
+    storage: &dyn Storage,
+    token_id: &str,
+    new_identity: &Addr,
+) -> Result<PendingRecovery, ContractError> {
+    match PENDING_RECOVERIES.may_load(storage, token_id)? {
+        Some(pending) if &pending.new_identity != new_identity => {
+            Err(ContractError::RecoveryMismatch {
+                pending: pending.new_identity.into(),
+            })
+        }
+        Some(pending) => Ok(pending),
+        None => Ok(PendingRecovery {
+            new_identity: new_identity.clone(),
+            approvals: vec![],
+            unlocks_at: None,
+        }),
+    }
+}
+
+// The JumpRing that issued a passport can recover it while it's still a
+// passport minter
+fn is_issuer(
+    contract: &Cw721MetadataContract,
+    deps: Deps,
+    info: &MessageInfo,
+    token: &TokenInfo<Extension>,
+) -> bool {
+    token.issuer.as_ref() == Some(&info.sender)
+        && contract.minters.has(deps.storage, &info.sender)
+}
@@ -2,7 +2,7 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use std::env::current_dir;
 use std::fs::create_dir_all;
 
-use portal::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use portal::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use portal::state::Config;
 
 fn main() {
@@ -14,5 +14,6 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema(&schema_for!(Config), &out_dir);
 }
\ No newline at end of file
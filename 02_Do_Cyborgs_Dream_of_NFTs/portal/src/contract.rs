@@ -1,24 +1,50 @@
+#[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cw2::set_contract_version;
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::query_fns::{jump_ring_check, minimum_sapience};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::migrate_fns::{migrate_legacy_config, upgrade};
+use crate::query_fns::{
+    attestation_nonce, attester, collected_fees, jump_ring_check, jump_ring_fee, known_portals, 
+    minimum_sapience, ownership, recent_travelers, status, travel_history, traveler_location,
+};
 use crate::execute_fns::{
-    initiate_jump_ring_travel, mint_passport, set_minimum_sapience, set_passport_contract, 
-    set_planet_name, set_potion_contract, set_sapient_names,
+    accept_ownership, arrive_from_jump_ring, cancel_ownership_proposal, complete_passport_recovery,
+    initiate_jump_ring_travel, mint_attested_passport, mint_passport, pause, propose_new_owner,
+    recover_passport, renew_passport, renounce_ownership, revoke_passport, set_attester,
+    set_guardians, set_jump_ring_fee, set_known_portals, set_minimum_sapience, 
+    set_passport_contract, set_planet_name, set_potion_contract, set_registry_contract, 
+    set_sapient_names, set_travel_log_limit, unpause, withdraw_fees,
 };
 use crate::state::{Config, CONFIG};
 
-#[entry_point]
+// Version info for migration
+const CONTRACT_NAME: &str = "crates.io:portal";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
-        QueryMsg::JumpRingPreCheck { traveler } => jump_ring_check(traveler),
+        QueryMsg::JumpRingPreCheck { traveler } => jump_ring_check(traveler, deps),
         QueryMsg::MinimumSapience {} => minimum_sapience(deps),
+        QueryMsg::KnownPortals {} => known_portals(deps),
+        QueryMsg::TravelerLocation { traveler } => traveler_location(traveler, deps),
+        QueryMsg::TravelHistory { traveler, start_after, limit } => {
+            travel_history(traveler, start_after, limit, deps)
+        }
+        QueryMsg::RecentTravelers { start_after, limit } => recent_travelers(start_after, limit, deps),
+        QueryMsg::JumpRingFee {} => jump_ring_fee(deps),
+        QueryMsg::CollectedFees {} => collected_fees(deps),
+        QueryMsg::Ownership {} => ownership(deps),
+        QueryMsg::Status {} => status(deps),
+        QueryMsg::Attester {} => attester(deps),
+        QueryMsg::AttestationNonce { identity } => attestation_nonce(identity, deps),
     }
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
     env: Env,
@@ -31,12 +57,33 @@ pub fn execute(
         ExecuteMsg::SetMinimumSapience { to } => set_minimum_sapience(to, deps, info),
         ExecuteMsg::SetPassportContract { contract } => set_passport_contract(contract, deps, info),
         ExecuteMsg::SetPotionContract { contract } => set_potion_contract(contract, deps, info),
+        ExecuteMsg::SetKnownPortals { to } => set_known_portals(to, deps, info),
+        ExecuteMsg::SetRegistryContract { contract } => set_registry_contract(contract, deps, info),
+        ExecuteMsg::SetTravelLogLimit { to } => set_travel_log_limit(to, deps, info),
+        ExecuteMsg::SetJumpRingFee { to } => set_jump_ring_fee(to, deps, info),
+        ExecuteMsg::WithdrawFees { to, amount } => withdraw_fees(to, amount, deps, info),
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            propose_new_owner(new_owner, expiry, deps, env, info)
+        }
+        ExecuteMsg::AcceptOwnership {} => accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipProposal {} => cancel_ownership_proposal(deps, info),
+        ExecuteMsg::RenounceOwnership {} => renounce_ownership(deps, info),
+        ExecuteMsg::SetGuardians { guardians } => set_guardians(guardians, deps, info),
+        ExecuteMsg::Pause { reason } => pause(reason, deps, env, info),
+        ExecuteMsg::Unpause {} => unpause(deps, info),
+        ExecuteMsg::SetAttester { attester } => set_attester(attester, deps, info),
         ExecuteMsg::MintPassport { msg } => mint_passport(msg, deps, env, info),
+        ExecuteMsg::MintAttestedPassport { msg, attestation } => mint_attested_passport(msg, attestation, deps, env),
+        ExecuteMsg::RenewPassport { msg } => renew_passport(msg, deps, info),
+        ExecuteMsg::RevokePassport { traveler, reason } => revoke_passport(traveler, reason, deps, info),
+        ExecuteMsg::RecoverPassport { traveler, new_identity } => recover_passport(traveler, new_identity, deps, info),
+        ExecuteMsg::CompletePassportRecovery { traveler, new_identity } => complete_passport_recovery(traveler, new_identity, deps, info),
         ExecuteMsg::JumpRingTravel { to, traveler } => initiate_jump_ring_travel(to, traveler, deps, env, info),
+        ExecuteMsg::ArriveFromJumpRing { traveler, origin } => arrive_from_jump_ring(traveler, origin, deps, env, info),
     }
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
@@ -44,15 +91,41 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     let config = Config {
-        owner: info.sender,
+        owner: Some(info.sender.clone()),
         planet_name: msg.planet_name,
         planet_sapients: msg.planet_sapients,
         minimum_sapience: msg.minimum_sapience,
         passport_contract: msg.passport_contract,
         potion_contract: msg.potion_contract,
+        registry_contract: msg.registry_contract,
+        jump_ring_fee: msg.jump_ring_fee,
     };
     CONFIG.save(deps.storage, &config)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     Ok(Response::new()
-        .add_attribute("owner", config.owner)
+        .add_attribute("owner", info.sender)
         .add_attribute("minimum_sapience", config.minimum_sapience.as_str()))
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::Upgrade {} => upgrade(CONTRACT_NAME, CONTRACT_VERSION, deps),
+        MigrateMsg::FromLegacy {
+            planet_sapients,
+            passport_contract,
+            potion_contract,
+            registry_contract,
+            jump_ring_fee,
+        } => migrate_legacy_config(
+            CONTRACT_NAME,
+            CONTRACT_VERSION,
+            planet_sapients,
+            passport_contract,
+            potion_contract,
+            registry_contract,
+            jump_ring_fee,
+            deps,
+        ),
+    }
+}
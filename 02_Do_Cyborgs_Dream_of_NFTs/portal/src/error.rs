@@ -1,5 +1,6 @@
 use cosmwasm_std::StdError;
 use thiserror::Error;
+use universe::migrate::MigrateError;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -9,6 +10,57 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("No ownership transfer has been proposed")]
+    NoPendingOwner {},
+
+    #[error("Ownership transfer proposal has expired")]
+    OwnershipProposalExpired {},
+
+    #[error("{0}")]
+    Migrate(#[from] MigrateError),
+
+    #[error("No course 01 config found to migrate")]
+    NoLegacyConfig {},
+
+    #[error("JumpRing is paused: {reason}")]
+    Paused { reason: String },
+
+    #[error("JumpRing is already paused")]
+    AlreadyPaused {},
+
+    #[error("JumpRing is not paused")]
+    NotPaused {},
+
     #[error("Carrying two passports is a crime")]
     IllegalAlien {},
+
+    #[error("Passport has been revoked: {reason}")]
+    PassportRevoked { reason: String },
+
+    #[error("Passport has expired")]
+    PassportExpired {},
+
+    #[error("Invalid cyborg DNA: {reason}")]
+    InvalidDna { reason: String },
+
+    #[error("JumpRing travel requires a fee of {required}")]
+    InsufficientFee { required: String },
+
+    #[error("Only {collected} has been collected")]
+    InsufficientCollectedFees { collected: String },
+
+    #[error("No known JumpRing at {portal}")]
+    UnknownPortal { portal: String },
+
+    #[error("JumpRing doesn't accept attested passports")]
+    NoAttester {},
+
+    #[error("Invalid attester key: {reason}")]
+    InvalidAttesterKey { reason: String },
+
+    #[error("Attestation signature doesn't match the attester key")]
+    InvalidAttestation {},
+
+    #[error("Expected attestation nonce {expected}, got {found}")]
+    InvalidNonce { expected: u64, found: u64 },
 }
@@ -1,18 +1,26 @@
 use cosmwasm_std::{
-    Addr, CosmosMsg, DepsMut, Env, MessageInfo, QueryRequest, 
-    to_binary, Response, WasmMsg, WasmQuery,
+    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, Event, MessageInfo, QueryRequest, 
+    to_binary, to_vec, Response, StdResult, Storage, Uint128, WasmMsg, WasmQuery,
 };
+use sha2::{Digest, Sha256};
 
-use cw721::{NftInfoResponse, TokensResponse};
+use cw721::{NftInfoResponse, TokenValidity, TokensResponse};
+use cw_utils::Expiration;
 use passport_token::{
-    ExecuteMsg as Cw721ExecuteMsg, Extension, Metadata, 
-    MintMsg as Cw721MintMsg, QueryMsg as Cw721QueryMsg,
+    ExecuteMsg as Cw721ExecuteMsg, Extension, Metadata, MintMsg as Cw721MintMsg, 
+    PassportExecuteMsg, QueryMsg as Cw721QueryMsg, VisaDirection,
 };
 
+use registry::msg::{PortalResponse, QueryMsg as RegistryQueryMsg};
+
 use crate::error::ContractError;
-use crate::state::CONFIG;
-use crate::msg::MintMsg;
+use crate::state::{
+    travel_log, AttesterKey, Config, PauseInfo, PendingOwner, TravelDirection, TravelRecord, COLLECTED_FEES, CONFIG, PENDING_OWNER, DEFAULT_TRAVEL_LOG_LIMIT, MAX_TRAVEL_LOG_PRUNE, 
+    ATTESTATION_NONCES, ATTESTER, GUARDIANS, KNOWN_PORTALS, PAUSED, TRAVEL_LOG_LIMIT, TRAVEL_LOG_NEXT_ID, TRAVEL_LOG_OLDEST_ID, TRAVELER_LOCATIONS,
+};
+use crate::msg::{Attestation, AttestationPayload, ExecuteMsg, MintMsg, RenewMsg};
 
+use universe::dna::CyborgDna;
 use universe::species::{SapienceScale, Sapient};
 
 pub fn mint_passport(
@@ -24,15 +32,67 @@ pub fn mint_passport(
     let config = CONFIG.load(deps.storage)?;
     
     // Only potion contract can call this function
-    let potion_contract = config.potion_contract;
-    if info.sender != potion_contract {
-        // XXX: Second `if` is for testing without instatiating all 3 contracts. Can
-        // be removed later; e.g. after potion contract is updated to support minting
-        if info.sender != config.owner {
-            return Err(ContractError::Unauthorized {});
+    if info.sender != config.potion_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_not_paused(deps.storage)?;
+    mint(msg, config, deps, env)
+}
+
+// Lets an off-chain service that vetted the traveler issue their passport
+// without holding the owner key. Anyone can relay the attested `MintMsg`
+pub fn mint_attested_passport(
+    msg: MintMsg,
+    attestation: Attestation,
+    deps: DepsMut,
+    env: Env,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    check_not_paused(deps.storage)?;
+    let attester = ATTESTER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoAttester {})?;
+
+    let expected = ATTESTATION_NONCES
+        .may_load(deps.storage, &msg.identity)?
+        .unwrap_or_default();
+    if attestation.nonce != expected {
+        return Err(ContractError::InvalidNonce { expected, found: attestation.nonce });
+    }
+
+    let payload = to_vec(&AttestationPayload {
+        portal: env.contract.address.clone(),
+        nonce: attestation.nonce,
+        msg: msg.clone(),
+    })?;
+    // A malformed signature is as invalid as one that doesn't match
+    let verified = match &attester {
+        AttesterKey::Secp256k1 { pubkey } => {
+            let hash = Sha256::digest(&payload);
+            deps.api.secp256k1_verify(&hash, &attestation.signature, pubkey)
+        }
+        AttesterKey::Ed25519 { pubkey } => {
+            deps.api.ed25519_verify(&payload, &attestation.signature, pubkey)
         }
     }
+    .unwrap_or(false);
+    if !verified {
+        return Err(ContractError::InvalidAttestation {});
+    }
+    ATTESTATION_NONCES.save(deps.storage, &msg.identity, &(expected + 1))?;
+
+    let res = mint(msg, config, deps, env)?;
+    Ok(res
+        .add_attribute("action", "mint_attested_passport")
+        .add_attribute("nonce", attestation.nonce.to_string()))
+}
 
+fn mint(
+    msg: MintMsg,
+    config: Config,
+    deps: DepsMut,
+    env: Env,
+) -> Result<Response, ContractError> {
     // Minting fails if user already owns a passport
     let query_msg: passport_token::QueryMsg<Extension> = Cw721QueryMsg::Tokens {
         owner: msg.identity.clone().into(),
@@ -48,11 +108,15 @@ pub fn mint_passport(
         return Err(ContractError::IllegalAlien {});
     }
 
+    // Passports only record DNA the potion could have generated
+    CyborgDna::validate(&msg.dna)
+        .map_err(|e| ContractError::InvalidDna { reason: e.to_string() })?;
+
     let metadata_extension: Extension = Some(Metadata {
         name: Some(msg.name),
         description: Some(msg.description),
         image: Some(msg.image),
-        dna: Some(msg.dna), // XXX TODO: Re-work the way DNA strings are built and parsed in Potion contract
+        dna: Some(msg.dna),
         species: Some(msg.species),
         sapience_level: Some(msg.sapience_level),
         issuer: Some(env.contract.address.clone()),
@@ -65,6 +129,8 @@ pub fn mint_passport(
         owner: msg.identity.into(),
         token_uri: None,
         extension: metadata_extension,
+        expires: None,
+        notify: None,
     });
 
     // Mint the passport
@@ -83,30 +149,47 @@ pub fn mint_passport(
 }
 
 pub fn initiate_jump_ring_travel(
-    _to: Addr,
+    to: Addr,
     traveler: Addr,
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
     // Only potion contract can call this function
-    let potion_contract = config.potion_contract;
-    if info.sender != potion_contract {
-        // XXX: Second `if` is for testing without instatiating all 3 contracts. Can
-        // be removed later; e.g. after potion contract is updated to support minting
-        if info.sender != config.owner {
-            return Err(ContractError::Unauthorized {});
+    if info.sender != config.potion_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+    check_not_paused(deps.storage)?;
+
+    // Travel must be paid for up front
+    if let Some(fee) = &config.jump_ring_fee {
+        let paid = info
+            .funds
+            .iter()
+            .any(|coin| coin.denom == fee.denom && coin.amount >= fee.amount);
+        if !paid {
+            return Err(ContractError::InsufficientFee { required: fee.to_string() });
         }
     }
+    for coin in &info.funds {
+        COLLECTED_FEES.update(deps.storage, &coin.denom, |collected| -> StdResult<_> {
+            Ok(collected.unwrap_or_default() + coin.amount)
+        })?;
+    }
+
+    // Travelers can only be sent to JumpRings we know about
+    if to == env.contract.address || !is_known_portal(&deps, &to)? {
+        return Err(ContractError::UnknownPortal { portal: to.into() });
+    }
 
     // Verify traveler's passport
     let query_msg: passport_token::QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
         token_id: traveler.clone().into(),
     };
     let query_req = QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: config.passport_contract.into(),
+        contract_addr: config.passport_contract.clone().into(),
         msg: to_binary(&query_msg).unwrap(),
     });
     let query_resp: NftInfoResponse<Metadata> = deps.querier.query(&query_req)?;
@@ -120,11 +203,378 @@ pub fn initiate_jump_ring_travel(
         return Err(ContractError::Unauthorized {});
     }
 
-    // XXX TODO: Process JumpRing travel -> _to: Addr
+    // Revoked and expired passports are kept on record, but aren't valid for travel
+    match query_resp.validity {
+        TokenValidity::Valid {} => {}
+        TokenValidity::Revoked { reason } => return Err(ContractError::PassportRevoked { reason }),
+        TokenValidity::Expired { .. } => return Err(ContractError::PassportExpired {}),
+    }
+
+    // Only cyborgs survive the JumpRing
+    CyborgDna::validate(&query_resp.extension.dna.unwrap_or_default())
+        .map_err(|e| ContractError::InvalidDna { reason: e.to_string() })?;
+
+    // The traveler is now in transit to the destination portal
+    TRAVELER_LOCATIONS.save(deps.storage, &traveler, &to)?;
+    record_travel(deps.storage, &env, &traveler, TravelDirection::Departure, &to)?;
+
+    // Stamp the traveler's exit visa, then hand them over to the destination JumpRing
+    let visa_resp = stamp_visa(
+        &config.passport_contract,
+        &traveler,
+        config.planet_name,
+        VisaDirection::Exit,
+    )?;
+    let arrival_msg = ExecuteMsg::ArriveFromJumpRing {
+        traveler: traveler.clone(),
+        origin: env.contract.address,
+    };
+    let travel_resp: CosmosMsg = WasmMsg::Execute {
+        contract_addr: to.clone().into(),
+        msg: to_binary(&arrival_msg)?,
+        funds: vec![],
+    }
+    .into();
 
     Ok(Response::new()
+        .add_message(visa_resp)
+        .add_message(travel_resp)
         .add_attribute("action", "initiate_jump_ring_travel")
-        .add_attribute("traveler", traveler))
+        .add_attribute("traveler", traveler)
+        .add_attribute("destination", to))
+}
+
+pub fn renew_passport(
+    msg: RenewMsg,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let query_msg: passport_token::QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: msg.traveler.clone().into(),
+    };
+    let query_req = QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.passport_contract.clone().into(),
+        msg: to_binary(&query_msg)?,
+    });
+    let query_resp: NftInfoResponse<Metadata> = deps.querier.query(&query_req)?;
+
+    // Only correct what's been asked for; the passport contract 
+    // rejects any changes to the traveler's DNA or identity
+    let mut metadata = query_resp.extension;
+    metadata.name = msg.name.or(metadata.name);
+    metadata.description = msg.description.or(metadata.description);
+    metadata.image = msg.image.or(metadata.image);
+    metadata.species = msg.species.or(metadata.species);
+    metadata.sapience_level = msg.sapience_level.or(metadata.sapience_level);
+
+    let update_msg: passport_token::ExecuteMsg = Cw721ExecuteMsg::UpdateMetadata {
+        token_id: msg.traveler.clone().into(),
+        extension: Some(metadata),
+    };
+    let update_resp: CosmosMsg = WasmMsg::Execute {
+        contract_addr: config.passport_contract.into(),
+        msg: to_binary(&update_msg)?,
+        funds: vec![],
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_message(update_resp)
+        .add_attribute("action", "renew_passport")
+        .add_attribute("traveler", msg.traveler))
+}
+
+pub fn revoke_passport(
+    traveler: Addr,
+    reason: String,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Passports are keyed by the traveler's address, and only
+    // this JumpRing (as the passport minter) can revoke them
+    let revoke_msg: passport_token::ExecuteMsg = Cw721ExecuteMsg::RevokeToken {
+        token_id: traveler.clone().into(),
+        reason: reason.clone(),
+    };
+    let revoke_resp: CosmosMsg = WasmMsg::Execute {
+        contract_addr: config.passport_contract.into(),
+        msg: to_binary(&revoke_msg)?,
+        funds: vec![],
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_message(revoke_resp)
+        .add_attribute("action", "revoke_passport")
+        .add_attribute("traveler", traveler)
+        .add_attribute("reason", reason))
+}
+
+// Travelers who've lost their keys can have the JumpRing that issued their passport
+// move it to a new identity. The passport contract only allows this after a timelock,
+// which gives anyone trying to steal a passport this way time to be stopped
+pub fn recover_passport(
+    traveler: Addr,
+    new_identity: Addr,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let request_msg: passport_token::ExecuteMsg = Cw721ExecuteMsg::Extension {
+        msg: PassportExecuteMsg::RequestRecovery {
+            token_id: traveler.clone().into(),
+            new_identity: new_identity.clone().into(),
+        },
+    };
+    let request_resp: CosmosMsg = WasmMsg::Execute {
+        contract_addr: config.passport_contract.into(),
+        msg: to_binary(&request_msg)?,
+        funds: vec![],
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_message(request_resp)
+        .add_attribute("action", "recover_passport")
+        .add_attribute("traveler", traveler)
+        .add_attribute("new_identity", new_identity))
+}
+
+pub fn complete_passport_recovery(
+    traveler: Addr,
+    new_identity: Addr,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let complete_msg: passport_token::ExecuteMsg = Cw721ExecuteMsg::Extension {
+        msg: PassportExecuteMsg::CompleteRecovery {
+            token_id: traveler.clone().into(),
+            new_identity: new_identity.clone().into(),
+        },
+    };
+    let complete_resp: CosmosMsg = WasmMsg::Execute {
+        contract_addr: config.passport_contract.into(),
+        msg: to_binary(&complete_msg)?,
+        funds: vec![],
+    }
+    .into();
+
+    // The traveler is still wherever they were, under their new identity.
+    // Their travel log stays under the old one, for audit
+    if let Some(location) = TRAVELER_LOCATIONS.may_load(deps.storage, &traveler)? {
+        TRAVELER_LOCATIONS.remove(deps.storage, &traveler);
+        TRAVELER_LOCATIONS.save(deps.storage, &new_identity, &location)?;
+    }
+
+    Ok(Response::new()
+        .add_message(complete_resp)
+        .add_attribute("action", "complete_passport_recovery")
+        .add_attribute("traveler", traveler)
+        .add_attribute("new_identity", new_identity))
+}
+
+pub fn arrive_from_jump_ring(
+    traveler: Addr,
+    origin: Addr,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Only known JumpRings can send travelers here, and 
+    // they can only send travelers from their own location
+    if info.sender != origin || !is_known_portal(&deps, &origin)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    TRAVELER_LOCATIONS.save(deps.storage, &traveler, &env.contract.address)?;
+    record_travel(deps.storage, &env, &traveler, TravelDirection::Arrival, &origin)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let visa_resp = stamp_visa(
+        &config.passport_contract,
+        &traveler,
+        config.planet_name,
+        VisaDirection::Entry,
+    )?;
+
+    Ok(Response::new()
+        .add_message(visa_resp)
+        .add_attribute("action", "arrive_from_jump_ring")
+        .add_attribute("traveler", traveler)
+        .add_attribute("origin", origin))
+}
+
+// JumpRings stamp visas as passport minters, so every JumpRing a traveler
+// passes through must be allowed to mint on the traveler's passport contract
+fn stamp_visa(
+    passport_contract: &Addr,
+    traveler: &Addr,
+    planet: String,
+    direction: VisaDirection,
+) -> StdResult<CosmosMsg> {
+    let stamp_msg: passport_token::ExecuteMsg = Cw721ExecuteMsg::Extension {
+        msg: PassportExecuteMsg::StampVisa {
+            token_id: traveler.into(),
+            planet,
+            direction,
+        },
+    };
+    Ok(WasmMsg::Execute {
+        contract_addr: passport_contract.into(),
+        msg: to_binary(&stamp_msg)?,
+        funds: vec![],
+    }
+    .into())
+}
+
+fn check_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
+    match PAUSED.may_load(storage)? {
+        Some(pause) => Err(ContractError::Paused { reason: pause.reason }),
+        None => Ok(()),
+    }
+}
+
+fn is_known_portal(deps: &DepsMut, portal: &Addr) -> Result<bool, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Portals listed in the JumpRing registry take precedence over 
+    // the locally configured list of known portals
+    if let Some(registry_contract) = config.registry_contract {
+        let query_req = QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: registry_contract.into(),
+            msg: to_binary(&RegistryQueryMsg::Portal { portal: portal.clone() })?,
+        });
+        let registered: StdResult<PortalResponse> = deps.querier.query(&query_req);
+        return Ok(registered.is_ok());
+    }
+
+    let known_portals = KNOWN_PORTALS.may_load(deps.storage)?.unwrap_or_default();
+    Ok(known_portals.contains(portal))
+}
+
+fn record_travel(
+    storage: &mut dyn Storage,
+    env: &Env,
+    traveler: &Addr,
+    direction: TravelDirection,
+    portal: &Addr,
+) -> Result<(), ContractError> {
+    let id = TRAVEL_LOG_NEXT_ID.may_load(storage)?.unwrap_or_default();
+    let record = TravelRecord {
+        traveler: traveler.clone(),
+        direction,
+        portal: portal.clone(),
+        height: env.block.height,
+        time: env.block.time,
+    };
+    travel_log().save(storage, id, &record)?;
+    TRAVEL_LOG_NEXT_ID.save(storage, &(id + 1))?;
+    prune_travel_log(storage)?;
+    Ok(())
+}
+
+// Drops the oldest travel log entries, at most `MAX_TRAVEL_LOG_PRUNE` of them,
+// towards fitting the log within its retention limit. Returns how many entries
+// are still over the limit
+fn prune_travel_log(storage: &mut dyn Storage) -> Result<u64, ContractError> {
+    let limit = TRAVEL_LOG_LIMIT.may_load(storage)?.unwrap_or(DEFAULT_TRAVEL_LOG_LIMIT);
+    let next_id = TRAVEL_LOG_NEXT_ID.may_load(storage)?.unwrap_or_default();
+    let oldest_id = TRAVEL_LOG_OLDEST_ID.may_load(storage)?.unwrap_or_default();
+    let excess = (next_id - oldest_id).saturating_sub(limit);
+    let pruned = excess.min(MAX_TRAVEL_LOG_PRUNE);
+    for id in oldest_id..oldest_id + pruned {
+        travel_log().remove(storage, id)?;
+    }
+    TRAVEL_LOG_OLDEST_ID.save(storage, &(oldest_id + pruned))?;
+    Ok(excess - pruned)
+}
+
+pub fn set_travel_log_limit(
+    to: u64,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    TRAVEL_LOG_LIMIT.save(deps.storage, &to)?;
+    // Setting the same limit again prunes another batch
+    let over_limit = prune_travel_log(deps.storage)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_travel_log_limit")
+        .add_attribute("limit", to.to_string())
+        .add_attribute("over_limit", over_limit.to_string()))
+}
+
+pub fn set_jump_ring_fee(
+    to: Option<Coin>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.jump_ring_fee = to;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "set_jump_ring_fee"))
+}
+
+pub fn withdraw_fees(
+    to: Addr,
+    amount: Coin,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let collected = COLLECTED_FEES
+        .may_load(deps.storage, &amount.denom)?
+        .unwrap_or_default();
+    if amount.amount > collected {
+        return Err(ContractError::InsufficientCollectedFees {
+            collected: Coin { denom: amount.denom, amount: collected }.to_string(),
+        });
+    }
+    let remaining = collected - amount.amount;
+    if remaining == Uint128::zero() {
+        COLLECTED_FEES.remove(deps.storage, &amount.denom);
+    } else {
+        COLLECTED_FEES.save(deps.storage, &amount.denom, &remaining)?;
+    }
+
+    let withdrawal = BankMsg::Send {
+        to_address: to.to_string(),
+        amount: vec![amount.clone()],
+    };
+    Ok(Response::new()
+        .add_message(withdrawal)
+        .add_attribute("action", "withdraw_fees")
+        .add_attribute("to", to)
+        .add_attribute("amount", amount.to_string()))
 }
 
 pub fn set_minimum_sapience(
@@ -133,7 +583,7 @@ pub fn set_minimum_sapience(
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if config.owner.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
     config.minimum_sapience = to;
@@ -147,7 +597,7 @@ pub fn set_planet_name(
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if config.owner.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
     config.planet_name = to;
@@ -161,7 +611,7 @@ pub fn set_sapient_names(
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if config.owner.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
     config.planet_sapients = to;
@@ -175,7 +625,7 @@ pub fn set_passport_contract(
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if config.owner.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
     config.passport_contract = contract;
@@ -183,16 +633,220 @@ pub fn set_passport_contract(
     Ok(Response::new().add_attribute("action", "set_passport_contract"))
 }
 
+pub fn set_known_portals(
+    to: Vec<Addr>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    KNOWN_PORTALS.save(deps.storage, &to)?;
+    Ok(Response::new().add_attribute("action", "set_known_portals"))
+}
+
+pub fn set_registry_contract(
+    contract: Option<Addr>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.registry_contract = contract;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "set_registry_contract"))
+}
+
 pub fn set_potion_contract(
     contract: Addr,
     deps: DepsMut,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
     let mut config = CONFIG.load(deps.storage)?;
-    if info.sender != config.owner {
+    if config.owner.as_ref() != Some(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
     config.potion_contract = contract;
     CONFIG.save(deps.storage, &config)?;
     Ok(Response::new().add_attribute("action", "set_potion_contract"))
-}
\ No newline at end of file
+}
+
+pub fn set_guardians(
+    guardians: Vec<Addr>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    GUARDIANS.save(deps.storage, &guardians)?;
+    Ok(Response::new().add_attribute("action", "set_guardians"))
+}
+
+// Only keys of the right length for their scheme can be set, so
+// a mistyped key is caught here rather than at the first mint
+pub fn set_attester(
+    attester: Option<AttesterKey>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    match &attester {
+        Some(AttesterKey::Secp256k1 { pubkey }) if ![33, 65].contains(&pubkey.len()) => {
+            return Err(ContractError::InvalidAttesterKey {
+                reason: format!("secp256k1 keys are 33 or 65 bytes, not {}", pubkey.len()),
+            });
+        }
+        Some(AttesterKey::Ed25519 { pubkey }) if pubkey.len() != 32 => {
+            return Err(ContractError::InvalidAttesterKey {
+                reason: format!("ed25519 keys are 32 bytes, not {}", pubkey.len()),
+            });
+        }
+        Some(key) => ATTESTER.save(deps.storage, key)?,
+        None => ATTESTER.remove(deps.storage),
+    }
+    Ok(Response::new()
+        .add_attribute("action", "set_attester")
+        .add_attribute("enabled", attester.is_some().to_string()))
+}
+
+pub fn pause(
+    reason: String,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let guardians = GUARDIANS.may_load(deps.storage)?.unwrap_or_default();
+    if config.owner.as_ref() != Some(&info.sender) && !guardians.contains(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if PAUSED.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyPaused {});
+    }
+
+    let pause = PauseInfo {
+        paused_by: info.sender.clone(),
+        reason: reason.clone(),
+        height: env.block.height,
+        time: env.block.time,
+    };
+    PAUSED.save(deps.storage, &pause)?;
+    let event = Event::new("jump_ring_paused")
+        .add_attribute("paused_by", info.sender)
+        .add_attribute("reason", reason);
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "pause"))
+}
+
+pub fn unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Guardians can only pull the emergency brake, not release it
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    let pause = PAUSED
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NotPaused {})?;
+
+    PAUSED.remove(deps.storage);
+    let event = Event::new("jump_ring_unpaused")
+        .add_attribute("unpaused_by", info.sender)
+        .add_attribute("paused_by", pause.paused_by)
+        .add_attribute("reason", pause.reason);
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("action", "unpause"))
+}
+
+pub fn propose_new_owner(
+    new_owner: Addr,
+    expiry: Option<Expiration>,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if expiry.map(|e| e.is_expired(&env.block)).unwrap_or(false) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    // A new proposal replaces any previous one
+    let pending = PendingOwner {
+        new_owner: new_owner.clone(),
+        expiry,
+    };
+    PENDING_OWNER.save(deps.storage, &pending)?;
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_owner")
+        .add_attribute("new_owner", new_owner))
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_OWNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner {})?;
+    if info.sender != pending.new_owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if pending.expiry.map(|e| e.is_expired(&env.block)).unwrap_or(false) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    let mut config = CONFIG.load(deps.storage)?;
+    config.owner = Some(pending.new_owner);
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("owner", info.sender))
+}
+
+pub fn cancel_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new().add_attribute("action", "cancel_ownership_proposal"))
+}
+
+pub fn renounce_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if config.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Without an owner, none of the owner-only settings can be changed again
+    config.owner = None;
+    CONFIG.save(deps.storage, &config)?;
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new().add_attribute("action", "renounce_ownership"))
+}
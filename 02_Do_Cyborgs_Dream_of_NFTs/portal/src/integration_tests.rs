@@ -1,24 +1,45 @@
 #![cfg(test)]
 use serde::{de::DeserializeOwned, Serialize};
 use cosmwasm_std::{
-    Addr, Coin, Empty, from_binary, QueryRequest, to_binary, 
-    StdError, Timestamp, Uint128, WasmQuery
+    Addr, Attribute, Binary, Coin, Empty, Event, from_binary, QueryRequest, to_binary, to_vec,
+    StdError, Storage, Timestamp, Uint128, WasmQuery
 };
 use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
 use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_utils::Expiration;
+use cw2::{get_contract_version, set_contract_version};
 
-use crate::contract::{instantiate as portal_instantiate, query as portal_query};
+use crate::error::ContractError;
+use crate::contract::{
+    execute as portal_execute, instantiate as portal_instantiate, migrate as portal_migrate,
+    query as portal_query,
+};
 use crate::msg::{
-    ExecuteMsg, InstantiateMsg, MintMsg, QueryMsg, 
+    Attestation, AttestationNonceResponse, AttestationPayload, AttesterResponse, 
+    CollectedFeesResponse, ExecuteMsg, FailedRequirement, InstantiateMsg, JumpRingCheckResponse, 
+    JumpRingFeeResponse, MigrateMsg, MintMsg, OwnershipResponse, QueryMsg, RenewMsg, 
+    StatusResponse, TravelLogResponse, TravelerLocationResponse,
+};
+use crate::migrate_fns::{legacy_key, LegacyConfig, LegacySapient};
+use crate::state::{
+    travel_log, AttesterKey, TravelDirection, TravelRecord, CONFIG, MAX_TRAVEL_LOG_PRUNE,
+    TRAVEL_LOG_NEXT_ID,
 };
+use registry::error::ContractError as RegistryContractError;
+use registry::msg::{
+    ExecuteMsg as RegistryExecuteMsg, InstantiateMsg as RegistryInstantiateMsg,
+};
+use universe::dna::CyborgDna;
+use universe::migrate::MigrateError;
 use universe::species::{
     SapienceResponse, SapienceScale, Sapient, Species
 };
 
-use cw721::{NftInfoResponse};
+use cw721::{NftInfoResponse, TokenValidity};
 use passport_token::{
     Extension, ExecuteMsg as Cw721ExecuteMsg, InstantiateMsg as Cw721InstantiateMsg,
-    Metadata, QueryMsg as Cw721QueryMsg,
+    Metadata, PassportQueryMsg, QueryMsg as Cw721QueryMsg, VisaDirection, VisasResponse,
+    RECOVERY_TIMELOCK_SECONDS,
 };
 
 pub static DENOM: &str = "uport";   // Fractional representation of the PORT coin 
@@ -28,6 +49,10 @@ pub static DENOM: &str = "uport";   // Fractional representation of the PORT coi
 // Refactor contract setup (store, instantiate for Portal and Passport contracts) 
 // into a separate helper function, and remove redundant code
 
+fn example_dna() -> String {
+    CyborgDna::new(vec![3, 9, 0, 4, 2]).to_string()
+}
+
 fn mock_app() -> App {
     App::default()
 }
@@ -91,6 +116,7 @@ fn create_cw721(
         name: "Passport Token".to_string(),
         symbol: "PASS".to_string(),
         minter: String::from(minter),
+        unique_owners: true,
     };
     let contract = router
         .instantiate_contract(cw721_id, minter.clone(), &msg, &[], "passport-v1.0", None)
@@ -98,13 +124,50 @@ fn create_cw721(
     contract
 }
 
+// Portals stamp visas as passport minters, so every portal a 
+// traveler passes through must be able to mint passports
+fn add_passport_minter(
+    router: &mut App,
+    nft_contract: &Addr,
+    admin: &Addr,
+    minter: &Addr,
+) {
+    let msg = Cw721ExecuteMsg::AddMinter {
+        minter: minter.to_string(),
+        quota: None,
+    };
+    router
+        .execute_contract(admin.clone(), nft_contract.clone(), &msg, &[])
+        .unwrap();
+}
+
+pub fn contract_registry() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        registry::contract::execute,
+        registry::contract::instantiate,
+        registry::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn create_registry(
+    router: &mut App,
+    owner: &Addr,
+) -> Addr {
+    let registry_id = router.store_code(contract_registry());
+    router
+        .instantiate_contract(registry_id, owner.clone(), &RegistryInstantiateMsg {}, &[], "registry-v1.0", None)
+        .unwrap()
+}
+
 fn create_portal(
     router: &mut App,
     owner: Addr,
+    planet_name: &str,
 ) -> Addr {
     let contract_id = router.store_code(contract_portal());
     let msg = InstantiateMsg {
-        planet_name: "earth".to_string(),
+        planet_name: planet_name.to_string(),
         planet_sapients: vec![
             Sapient {
                 name: "Some Cyborg".to_string(),
@@ -126,6 +189,8 @@ fn create_portal(
         minimum_sapience: SapienceScale::High,
         passport_contract: Addr::unchecked("portal"),   // Must be updated after instantiation and token creation
         potion_contract: Addr::unchecked("potion"),     // Must be updated after instantiation and potion creation
+        registry_contract: None,
+        jump_ring_fee: None,
     };
     let contract = router
         .instantiate_contract(contract_id, owner, &msg, &[], "portal-v1.0", None)
@@ -164,6 +229,8 @@ pub fn checking_minimum_sapience_level() {
         minimum_sapience: SapienceScale::High,
         passport_contract: Addr::unchecked("archway1yvnw8xj5elngcq95e2n2p8f80zl7shfwyxk88858pl6cgzveeqtqy7xtf7"),
         potion_contract: Addr::unchecked("archway1u6clujjm2qnem09gd4y7hhmulftvlt6mej4q0dd742tzcnsstt2q70lpu6"),
+        registry_contract: None,
+        jump_ring_fee: None,
     };
 
     portal_instantiate(deps.as_mut(), env.clone(), info, init_msg).unwrap();
@@ -180,6 +247,7 @@ pub fn checking_minimum_sapience_level() {
 pub fn minting_passport() {
     let mut app = mock_app();
     let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
     let user = Addr::unchecked("user");
     let current_time = get_block_time(&mut app);
     increment_block_time(&mut app, current_time + 1000, 7);
@@ -203,6 +271,7 @@ pub fn minting_passport() {
     let portal_contract = create_portal(
         &mut app,
         owner.clone(),
+        "earth",
     );
 
     // Instance of passport token contract
@@ -225,14 +294,14 @@ pub fn minting_passport() {
             name: "Traveler Name".to_string(),
             description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
             image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
-            dna: "Example DNA String".to_string(),
+            dna: example_dna(),
             species: "Cyborg".to_string(),
             sapience_level: SapienceScale::High,
             identity: user.clone(),
         }
     };
     let mint_res = app.execute_contract(
-        owner.clone(), 
+        potion.clone(), 
         portal_contract.clone(), 
         &mint_msg, 
         &[]
@@ -240,12 +309,21 @@ pub fn minting_passport() {
     // println!("{:?}", &mint_res);
     assert!(mint_res.is_ok());
 
+    // Only the potion contract can mint passports
+    let owner_mint_res = app.execute_contract(
+        owner.clone(), 
+        portal_contract.clone(), 
+        &mint_msg, 
+        &[]
+    );
+    assert!(owner_mint_res.is_err());
+
     // Verify metadata is correct for first minted passport
     let metadata_extension = Some(Metadata {
         name: Some("Traveler Name".to_string()),
         description: Some("Ever since you became a cyborg, you've been feeling pretty weird...".into()),
         image: Some("ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string()),
-        dna: Some("Example DNA String".to_string()),
+        dna: Some(example_dna()),
         species: Some("Cyborg".to_string()),
         sapience_level: Some(SapienceScale::High),
         issuer: Some(Addr::unchecked("contract0")),
@@ -262,6 +340,7 @@ pub fn minting_passport() {
         NftInfoResponse::<Extension> {
             token_uri: None,
             extension: metadata_extension.clone(),
+            validity: TokenValidity::Valid {},
         }
     );
 
@@ -278,7 +357,7 @@ pub fn minting_passport() {
         }
     };
     let minting_again_fails = app.execute_contract(
-        owner.clone(), 
+        potion.clone(), 
         portal_contract.clone(), 
         &mint_msg2, 
         &[]
@@ -299,7 +378,7 @@ pub fn minting_passport() {
     // println!("{:?}", &burn_res);
     assert!(burn_res.is_ok());
     let second_mint_res = app.execute_contract(
-        owner.clone(), 
+        potion.clone(), 
         portal_contract.clone(), 
         &mint_msg, 
         &[]
@@ -316,6 +395,7 @@ pub fn minting_passport() {
         NftInfoResponse::<Extension> {
             token_uri: None,
             extension: metadata_extension,
+            validity: TokenValidity::Valid {},
         }
     );
 }
@@ -328,9 +408,9 @@ pub fn minting_passport() {
 pub fn initiating_jump_ring_travel() {
     let mut app = mock_app();
     let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
     let user = Addr::unchecked("user");
     let another_user = Addr::unchecked("random");
-    let another_portal = Addr::unchecked("jupiter");
     let current_time = get_block_time(&mut app);
     increment_block_time(&mut app, current_time + 1000, 7);
     assert_eq!(get_block_time(&mut app), current_time + 1000);
@@ -353,6 +433,14 @@ pub fn initiating_jump_ring_travel() {
     let portal_contract = create_portal(
         &mut app,
         owner.clone(),
+        "earth",
+    );
+
+    // Instance of destination portal contract
+    let another_portal = create_portal(
+        &mut app,
+        owner.clone(),
+        "jupiter",
     );
 
     // Instance of passport token contract
@@ -362,12 +450,10 @@ pub fn initiating_jump_ring_travel() {
     let update_msg = ExecuteMsg::SetPassportContract {
         contract: nft_contract.clone(),
     };
-    let _portal_update = app.execute_contract(
-        owner.clone(), 
-        portal_contract.clone(), 
-        &update_msg, 
-        &[]
-    );
+    for portal in [&portal_contract, &another_portal] {
+        app.execute_contract(owner.clone(), portal.clone(), &update_msg, &[]).unwrap();
+    }
+    add_passport_minter(&mut app, &nft_contract, &portal_contract, &another_portal);
 
     // Mint first passport
     let mint_msg = ExecuteMsg::MintPassport {
@@ -375,27 +461,62 @@ pub fn initiating_jump_ring_travel() {
             name: "Traveler Name".to_string(),
             description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
             image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
-            dna: "Example DNA String".to_string(),
+            dna: example_dna(),
             species: "Cyborg".to_string(),
             sapience_level: SapienceScale::High,
             identity: user.clone(),
         }
     };
     let mint_res = app.execute_contract(
-        owner.clone(), 
+        potion.clone(), 
         portal_contract.clone(), 
         &mint_msg, 
         &[]
     );
     assert!(mint_res.is_ok());
 
+    // Traveling to a portal that hasn't been registered as known fails
+    let travel_msg = ExecuteMsg::JumpRingTravel { 
+        to: another_portal.clone(), 
+        traveler: user.clone(),
+    };
+    let unknown_portal_res = app.execute_contract(
+        potion.clone(), 
+        portal_contract.clone(), 
+        &travel_msg,
+        &[]
+    );
+    assert!(unknown_portal_res.is_err());
+
+    // Both portals must know each other for travel to succeed
+    let known_portals_msg = ExecuteMsg::SetKnownPortals {
+        to: vec![another_portal.clone()],
+    };
+    app.execute_contract(
+        owner.clone(), 
+        portal_contract.clone(), 
+        &known_portals_msg, 
+        &[]
+    )
+    .unwrap();
+    let known_portals_msg = ExecuteMsg::SetKnownPortals {
+        to: vec![portal_contract.clone()],
+    };
+    app.execute_contract(
+        owner.clone(), 
+        another_portal.clone(), 
+        &known_portals_msg, 
+        &[]
+    )
+    .unwrap();
+
     // Traveling without a valid passport fails
     let failing_travel_msg = ExecuteMsg::JumpRingTravel {
         to: another_portal.clone(), 
         traveler: another_user,
     };
     let failed_travel_res = app.execute_contract(
-        owner.clone(), 
+        potion.clone(), 
         portal_contract.clone(), 
         &failing_travel_msg,
         &[]
@@ -404,16 +525,1237 @@ pub fn initiating_jump_ring_travel() {
     assert!(failed_travel_res.is_err());
 
     // Traveling with a valid passport succeeds
-    let travel_msg = ExecuteMsg::JumpRingTravel { 
-        to: another_portal, 
-        traveler: user.clone(),
-    };
     let travel_res = app.execute_contract(
-        owner.clone(), 
+        potion.clone(), 
         portal_contract.clone(), 
         &travel_msg,
         &[]
     );
     // println!("{:?}", &travel_res);
     assert!(travel_res.is_ok());
-}
\ No newline at end of file
+
+    // Both portals agree the traveler is now on jupiter
+    let location_query = QueryMsg::TravelerLocation { 
+        traveler: user.clone(),
+    };
+    let origin_location: TravelerLocationResponse = query(
+        &mut app, 
+        portal_contract.clone(), 
+        location_query.clone(),
+    ).unwrap();
+    assert_eq!(origin_location.location, Some(another_portal.clone()));
+    let destination_location: TravelerLocationResponse = query(
+        &mut app, 
+        another_portal.clone(), 
+        location_query,
+    ).unwrap();
+    assert_eq!(destination_location.location, Some(another_portal.clone()));
+
+    // The traveler's passport was stamped on the way out of earth and into jupiter
+    let visas_query: Cw721QueryMsg<PassportQueryMsg> = Cw721QueryMsg::Extension {
+        msg: PassportQueryMsg::Visas {
+            token_id: user.to_string(),
+            start_after: None,
+            limit: None,
+        },
+    };
+    let visas: VisasResponse = query(&mut app, nft_contract, visas_query).unwrap();
+    let stamps: Vec<(Addr, String, VisaDirection)> = visas
+        .visas
+        .into_iter()
+        .map(|entry| (entry.visa.portal, entry.visa.planet, entry.visa.direction))
+        .collect();
+    assert_eq!(
+        stamps,
+        vec![
+            (portal_contract.clone(), "earth".to_string(), VisaDirection::Exit),
+            (another_portal.clone(), "jupiter".to_string(), VisaDirection::Entry),
+        ]
+    );
+
+    // Only known portals can announce arriving travelers
+    let fake_arrival_msg = ExecuteMsg::ArriveFromJumpRing {
+        traveler: user.clone(),
+        origin: owner.clone(),
+    };
+    let fake_arrival_res = app.execute_contract(
+        owner.clone(), 
+        another_portal, 
+        &fake_arrival_msg,
+        &[]
+    );
+    assert!(fake_arrival_res.is_err());
+}
+
+/// Verifies that arrivals and departures are logged by both portals, 
+/// and that only the most recent entries are retained
+#[test]
+pub fn auditing_travel_history() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let travelers = vec![Addr::unchecked("alice"), Addr::unchecked("bob")];
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let jupiter = create_portal(&mut app, owner.clone(), "jupiter");
+    let nft_contract = create_cw721(&mut app, &earth);
+    for (portal, other) in [(&earth, &jupiter), (&jupiter, &earth)] {
+        let update_msgs = vec![
+            ExecuteMsg::SetPassportContract { contract: nft_contract.clone() },
+            ExecuteMsg::SetKnownPortals { to: vec![other.clone()] },
+        ];
+        for msg in update_msgs {
+            app.execute_contract(owner.clone(), portal.clone(), &msg, &[]).unwrap();
+        }
+    }
+    add_passport_minter(&mut app, &nft_contract, &earth, &jupiter);
+    for traveler in &travelers {
+    let mint_msg = ExecuteMsg::MintPassport {
+            msg: MintMsg {
+                name: "Traveler Name".to_string(),
+                description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+                image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+                dna: example_dna(),
+                species: "Cyborg".to_string(),
+                sapience_level: SapienceScale::High,
+                identity: traveler.clone(),
+            }
+        };
+        app.execute_contract(potion.clone(), earth.clone(), &mint_msg, &[]).unwrap();
+    }
+
+    // Alice goes to jupiter and back, then bob goes to jupiter
+    let trips = vec![
+        (&travelers[0], &earth, &jupiter),
+        (&travelers[0], &jupiter, &earth),
+        (&travelers[1], &earth, &jupiter),
+    ];
+    for (traveler, from, to) in trips {
+        let travel_msg = ExecuteMsg::JumpRingTravel {
+            to: to.clone(),
+            traveler: traveler.clone(),
+        };
+        app.execute_contract(potion.clone(), from.clone(), &travel_msg, &[]).unwrap();
+    }
+
+    // Earth saw alice leave, alice arrive, then bob leave
+    let log: TravelLogResponse = query(
+        &mut app,
+        earth.clone(),
+        QueryMsg::RecentTravelers { start_after: None, limit: None },
+    ).unwrap();
+    let summary: Vec<(u64, Addr, TravelDirection, Addr)> = log
+        .entries
+        .into_iter()
+        .map(|e| (e.id, e.record.traveler, e.record.direction, e.record.portal))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (2, travelers[1].clone(), TravelDirection::Departure, jupiter.clone()),
+            (1, travelers[0].clone(), TravelDirection::Arrival, jupiter.clone()),
+            (0, travelers[0].clone(), TravelDirection::Departure, jupiter.clone()),
+        ]
+    );
+
+    // Alice's history on jupiter, one entry at a time
+    let history_query = |start_after| QueryMsg::TravelHistory {
+        traveler: travelers[0].clone(),
+        start_after,
+        limit: Some(1),
+    };
+    let page: TravelLogResponse = query(&mut app, jupiter.clone(), history_query(None)).unwrap();
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.entries[0].record.direction, TravelDirection::Departure);
+    assert_eq!(page.entries[0].record.portal, earth);
+    let next_page: TravelLogResponse = query(
+        &mut app, 
+        jupiter.clone(), 
+        history_query(Some(page.entries[0].id)),
+    ).unwrap();
+    assert_eq!(next_page.entries.len(), 1);
+    assert_eq!(next_page.entries[0].record.direction, TravelDirection::Arrival);
+    let last_page: TravelLogResponse = query(
+        &mut app, 
+        jupiter.clone(), 
+        history_query(Some(next_page.entries[0].id)),
+    ).unwrap();
+    assert!(last_page.entries.is_empty());
+
+    // Only the owner can change the retention limit
+    let limit_msg = ExecuteMsg::SetTravelLogLimit { to: 1 };
+    let err = app
+        .execute_contract(potion, earth.clone(), &limit_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // Lowering the limit drops all but the newest entries
+    app.execute_contract(owner, earth.clone(), &limit_msg, &[]).unwrap();
+    let log: TravelLogResponse = query(
+        &mut app,
+        earth.clone(),
+        QueryMsg::RecentTravelers { start_after: None, limit: None },
+    ).unwrap();
+    assert_eq!(log.entries.len(), 1);
+    assert_eq!(log.entries[0].record.traveler, travelers[1]);
+    let history: TravelLogResponse = query(
+        &mut app,
+        earth,
+        QueryMsg::TravelHistory { traveler: travelers[0].clone(), start_after: None, limit: None },
+    ).unwrap();
+    assert!(history.entries.is_empty());
+}
+
+/// Verifies that a big drop in the travel log limit is 
+/// pruned a bounded batch at a time
+#[test]
+pub fn pruning_travel_log_in_batches() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let owner = mock_info("owner", &[]);
+    let init_msg = InstantiateMsg {
+        planet_name: "earth".to_string(),
+        planet_sapients: vec![],
+        minimum_sapience: SapienceScale::High,
+        passport_contract: Addr::unchecked("passport"),
+        potion_contract: Addr::unchecked("potion"),
+        registry_contract: None,
+        jump_ring_fee: None,
+    };
+    portal_instantiate(deps.as_mut(), env.clone(), owner.clone(), init_msg).unwrap();
+
+    // A log longer than one batch
+    let entries = MAX_TRAVEL_LOG_PRUNE + 10;
+    for id in 0..entries {
+        let record = TravelRecord {
+            traveler: Addr::unchecked("traveler"),
+            direction: TravelDirection::Departure,
+            portal: Addr::unchecked("jupiter"),
+            height: env.block.height,
+            time: env.block.time,
+        };
+        travel_log().save(&mut deps.storage, id, &record).unwrap();
+    }
+    TRAVEL_LOG_NEXT_ID.save(&mut deps.storage, &entries).unwrap();
+
+    // Dropping the limit to nothing only prunes the first batch
+    let limit_msg = ExecuteMsg::SetTravelLogLimit { to: 0 };
+    let res = portal_execute(deps.as_mut(), env.clone(), owner.clone(), limit_msg.clone()).unwrap();
+    assert!(res.attributes.contains(&Attribute::new("over_limit", "10")));
+    let recent = QueryMsg::RecentTravelers { start_after: None, limit: Some(30) };
+    let log: TravelLogResponse = from_binary(&portal_query(deps.as_ref(), env.clone(), recent.clone()).unwrap()).unwrap();
+    assert_eq!(log.entries.len(), 10);
+    assert_eq!(log.entries[9].id, MAX_TRAVEL_LOG_PRUNE);
+
+    // Setting it again prunes the rest
+    let res = portal_execute(deps.as_mut(), env.clone(), owner, limit_msg).unwrap();
+    assert!(res.attributes.contains(&Attribute::new("over_limit", "0")));
+    let log: TravelLogResponse = from_binary(&portal_query(deps.as_ref(), env, recent).unwrap()).unwrap();
+    assert!(log.entries.is_empty());
+}
+
+/// Verifies that travel fees are charged, accumulated per denom 
+/// and can only be withdrawn by the owner
+#[test]
+pub fn collecting_jump_ring_fees() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let user = Addr::unchecked("user");
+    let treasury = Addr::unchecked("treasury");
+    mint_native(
+        &mut app,
+        potion.to_string(),
+        String::from(DENOM),
+        Uint128::from(10000000u128),
+    );
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let jupiter = create_portal(&mut app, owner.clone(), "jupiter");
+    let nft_contract = create_cw721(&mut app, &earth);
+    let fee = Coin {
+        denom: DENOM.to_string(),
+        amount: Uint128::from(1000000u128),
+    };
+    for (portal, other) in [(&earth, &jupiter), (&jupiter, &earth)] {
+        let update_msgs = vec![
+            ExecuteMsg::SetPassportContract { contract: nft_contract.clone() },
+            ExecuteMsg::SetKnownPortals { to: vec![other.clone()] },
+            ExecuteMsg::SetJumpRingFee { to: Some(fee.clone()) },
+        ];
+        for msg in update_msgs {
+            app.execute_contract(owner.clone(), portal.clone(), &msg, &[]).unwrap();
+        }
+    }
+    add_passport_minter(&mut app, &nft_contract, &earth, &jupiter);
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: example_dna(),
+            species: "Cyborg".to_string(),
+            sapience_level: SapienceScale::High,
+            identity: user.clone(),
+        }
+    };
+    app.execute_contract(potion.clone(), earth.clone(), &mint_msg, &[]).unwrap();
+
+    let fee_res: JumpRingFeeResponse = query(&mut app, earth.clone(), QueryMsg::JumpRingFee {}).unwrap();
+    assert_eq!(fee_res.fee, Some(fee.clone()));
+
+    // Unpaid travel fails
+    let travel_msg = ExecuteMsg::JumpRingTravel {
+        to: jupiter.clone(),
+        traveler: user,
+    };
+    let err = app
+        .execute_contract(potion.clone(), earth.clone(), &travel_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientFee { required: "1000000uport".to_string() },
+        err.downcast().unwrap()
+    );
+
+    // Paid travel succeeds and the fee is collected
+    let funds = vec![fee.clone()];
+    app.execute_contract(potion.clone(), earth.clone(), &travel_msg, &funds).unwrap();
+    let collected: CollectedFeesResponse = query(&mut app, earth.clone(), QueryMsg::CollectedFees {}).unwrap();
+    assert_eq!(collected.fees, funds);
+
+    // Only the owner can withdraw, and only what has been collected
+    let withdraw_msg = ExecuteMsg::WithdrawFees {
+        to: treasury.clone(),
+        amount: Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(400000u128),
+        },
+    };
+    let err = app
+        .execute_contract(potion, earth.clone(), &withdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let overdraw_msg = ExecuteMsg::WithdrawFees {
+        to: treasury.clone(),
+        amount: Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(2000000u128),
+        },
+    };
+    let err = app
+        .execute_contract(owner.clone(), earth.clone(), &overdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientCollectedFees { collected: "1000000uport".to_string() },
+        err.downcast().unwrap()
+    );
+
+    app.execute_contract(owner, earth.clone(), &withdraw_msg, &[]).unwrap();
+    let balance = app.wrap().query_balance(treasury, DENOM).unwrap();
+    assert_eq!(balance.amount, Uint128::from(400000u128));
+    let collected: CollectedFeesResponse = query(&mut app, earth, QueryMsg::CollectedFees {}).unwrap();
+    assert_eq!(
+        collected.fees,
+        vec![Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(600000u128),
+        }]
+    );
+}
+
+/// Verifies guardians can pause the JumpRing but only the owner
+/// can unpause it, and that travel and minting halt while paused
+#[test]
+pub fn pausing_jump_ring() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let guardian = Addr::unchecked("guardian");
+    let potion = Addr::unchecked("potion");
+    let user = Addr::unchecked("user");
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let jupiter = create_portal(&mut app, owner.clone(), "jupiter");
+    let nft_contract = create_cw721(&mut app, &earth);
+    for (portal, other) in [(&earth, &jupiter), (&jupiter, &earth)] {
+        let update_msgs = vec![
+            ExecuteMsg::SetPassportContract { contract: nft_contract.clone() },
+            ExecuteMsg::SetKnownPortals { to: vec![other.clone()] },
+        ];
+        for msg in update_msgs {
+            app.execute_contract(owner.clone(), portal.clone(), &msg, &[]).unwrap();
+        }
+    }
+    add_passport_minter(&mut app, &nft_contract, &earth, &jupiter);
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: example_dna(),
+            species: "Cyborg".to_string(),
+            sapience_level: SapienceScale::High,
+            identity: user.clone(),
+        }
+    };
+    let travel_msg = ExecuteMsg::JumpRingTravel {
+        to: jupiter,
+        traveler: user,
+    };
+
+    // Only the owner can appoint guardians, and only they or the owner can pause
+    let guardians_msg = ExecuteMsg::SetGuardians { guardians: vec![guardian.clone()] };
+    let pause_msg = ExecuteMsg::Pause { reason: "Ring instability".to_string() };
+    let err = app
+        .execute_contract(guardian.clone(), earth.clone(), &guardians_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let err = app
+        .execute_contract(guardian.clone(), earth.clone(), &pause_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(owner.clone(), earth.clone(), &guardians_msg, &[]).unwrap();
+
+    let res = app.execute_contract(guardian.clone(), earth.clone(), &pause_msg, &[]).unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-jump_ring_paused")
+            .add_attribute("paused_by", guardian.clone())
+            .add_attribute("reason", "Ring instability")
+    ));
+    let status: StatusResponse = query(&mut app, earth.clone(), QueryMsg::Status {}).unwrap();
+    let paused = status.paused.unwrap();
+    assert_eq!(paused.paused_by, guardian);
+    assert_eq!(paused.reason, "Ring instability".to_string());
+    assert_eq!(status.guardians, vec![guardian.clone()]);
+    let err = app
+        .execute_contract(owner.clone(), earth.clone(), &pause_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::AlreadyPaused {}, err.downcast().unwrap());
+
+    // Neither minting nor travel are possible while paused
+    for msg in [&mint_msg, &travel_msg] {
+        let err = app
+            .execute_contract(potion.clone(), earth.clone(), msg, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Paused { reason: "Ring instability".to_string() },
+            err.downcast().unwrap()
+        );
+    }
+
+    // Guardians can't unpause
+    let unpause_msg = ExecuteMsg::Unpause {};
+    let err = app
+        .execute_contract(guardian, earth.clone(), &unpause_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let res = app.execute_contract(owner.clone(), earth.clone(), &unpause_msg, &[]).unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-jump_ring_unpaused").add_attribute("unpaused_by", owner.clone())
+    ));
+    let err = app
+        .execute_contract(owner, earth.clone(), &unpause_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NotPaused {}, err.downcast().unwrap());
+    let status: StatusResponse = query(&mut app, earth.clone(), QueryMsg::Status {}).unwrap();
+    assert_eq!(status.paused, None);
+
+    // Business as usual
+    app.execute_contract(potion.clone(), earth.clone(), &mint_msg, &[]).unwrap();
+    app.execute_contract(potion, earth, &travel_msg, &[]).unwrap();
+}
+
+/// Verifies the owner can correct a traveler's passport
+#[test]
+pub fn renewing_passports() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let user = Addr::unchecked("user");
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let nft_contract = create_cw721(&mut app, &earth);
+    let update_msg = ExecuteMsg::SetPassportContract { contract: nft_contract.clone() };
+    app.execute_contract(owner.clone(), earth.clone(), &update_msg, &[]).unwrap();
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: example_dna(),
+            species: "Human".to_string(),
+            sapience_level: SapienceScale::Medium,
+            identity: user.clone(),
+        }
+    };
+    app.execute_contract(potion.clone(), earth.clone(), &mint_msg, &[]).unwrap();
+
+    let renew_msg = ExecuteMsg::RenewPassport {
+        msg: RenewMsg {
+            traveler: user.clone(),
+            name: None,
+            description: None,
+            image: None,
+            species: Some("Cyborg".to_string()),
+            sapience_level: Some(SapienceScale::High),
+        }
+    };
+    let err = app
+        .execute_contract(potion, earth.clone(), &renew_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(owner, earth.clone(), &renew_msg, &[]).unwrap();
+
+    // Only the corrected fields have changed
+    let query_msg: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: user.to_string(),
+    };
+    let passport: NftInfoResponse<Extension> = query(&mut app, nft_contract, query_msg).unwrap();
+    let metadata = passport.extension.unwrap();
+    assert_eq!(metadata.species, Some("Cyborg".to_string()));
+    assert_eq!(metadata.sapience_level, Some(SapienceScale::High));
+    assert_eq!(metadata.name, Some("Traveler Name".to_string()));
+    assert_eq!(metadata.dna, Some(example_dna()));
+    assert_eq!(metadata.identity, Some(user.clone()));
+    assert_eq!(metadata.issuer, Some(earth.clone()));
+
+    let check: JumpRingCheckResponse = query(
+        &mut app,
+        earth,
+        QueryMsg::JumpRingPreCheck { traveler: user },
+    ).unwrap();
+    assert!(check.valid);
+}
+
+/// Verifies only the owner can revoke passports, and that
+/// revoked passports can no longer be used for travel
+#[test]
+pub fn revoking_passports() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let user = Addr::unchecked("user");
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let jupiter = create_portal(&mut app, owner.clone(), "jupiter");
+    let nft_contract = create_cw721(&mut app, &earth);
+    for (portal, other) in [(&earth, &jupiter), (&jupiter, &earth)] {
+        let update_msgs = vec![
+            ExecuteMsg::SetPassportContract { contract: nft_contract.clone() },
+            ExecuteMsg::SetKnownPortals { to: vec![other.clone()] },
+        ];
+        for msg in update_msgs {
+            app.execute_contract(owner.clone(), portal.clone(), &msg, &[]).unwrap();
+        }
+    }
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: example_dna(),
+            species: "Cyborg".to_string(),
+            sapience_level: SapienceScale::High,
+            identity: user.clone(),
+        }
+    };
+    app.execute_contract(potion.clone(), earth.clone(), &mint_msg, &[]).unwrap();
+
+    let revoke_msg = ExecuteMsg::RevokePassport {
+        traveler: user.clone(),
+        reason: "Smuggling tribbles".to_string(),
+    };
+    let err = app
+        .execute_contract(potion.clone(), earth.clone(), &revoke_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(owner, earth.clone(), &revoke_msg, &[]).unwrap();
+
+    // The passport is kept on record, but marked as revoked
+    let query_msg: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: user.to_string(),
+    };
+    let passport: NftInfoResponse<Extension> = query(&mut app, nft_contract, query_msg).unwrap();
+    let revoked = TokenValidity::Revoked {
+        reason: "Smuggling tribbles".to_string(),
+    };
+    assert_eq!(passport.validity, revoked);
+
+    let check: JumpRingCheckResponse = query(
+        &mut app,
+        earth.clone(),
+        QueryMsg::JumpRingPreCheck { traveler: user.clone() },
+    ).unwrap();
+    assert_eq!(
+        check.failed_requirements,
+        vec![FailedRequirement::PassportRevoked { reason: "Smuggling tribbles".to_string() }]
+    );
+
+    let travel_msg = ExecuteMsg::JumpRingTravel {
+        to: jupiter,
+        traveler: user,
+    };
+    let err = app
+        .execute_contract(potion, earth, &travel_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PassportRevoked { reason: "Smuggling tribbles".to_string() },
+        err.downcast().unwrap()
+    );
+}
+
+/// Verifies the issuing portal can move a lost passport to a new identity,
+/// but only after the passport contract's recovery timelock
+#[test]
+pub fn recovering_passports() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let user = Addr::unchecked("user");
+    let new_user = Addr::unchecked("new_user");
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let jupiter = create_portal(&mut app, owner.clone(), "jupiter");
+    let nft_contract = create_cw721(&mut app, &earth);
+    for (portal, other) in [(&earth, &jupiter), (&jupiter, &earth)] {
+        let update_msgs = vec![
+            ExecuteMsg::SetPassportContract { contract: nft_contract.clone() },
+            ExecuteMsg::SetKnownPortals { to: vec![other.clone()] },
+        ];
+        for msg in update_msgs {
+            app.execute_contract(owner.clone(), portal.clone(), &msg, &[]).unwrap();
+        }
+    }
+    add_passport_minter(&mut app, &nft_contract, &earth, &jupiter);
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: example_dna(),
+            species: "Cyborg".to_string(),
+            sapience_level: SapienceScale::High,
+            identity: user.clone(),
+        }
+    };
+    app.execute_contract(potion.clone(), earth.clone(), &mint_msg, &[]).unwrap();
+    let travel_msg = ExecuteMsg::JumpRingTravel {
+        to: jupiter.clone(),
+        traveler: user.clone(),
+    };
+    app.execute_contract(potion.clone(), earth.clone(), &travel_msg, &[]).unwrap();
+
+    // Only the portal owner can recover passports
+    let recover_msg = ExecuteMsg::RecoverPassport {
+        traveler: user.clone(),
+        new_identity: new_user.clone(),
+    };
+    let err = app
+        .execute_contract(potion, earth.clone(), &recover_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(owner.clone(), earth.clone(), &recover_msg, &[]).unwrap();
+
+    // The recovery can't be completed until its timelock has passed
+    let complete_msg = ExecuteMsg::CompletePassportRecovery {
+        traveler: user.clone(),
+        new_identity: new_user.clone(),
+    };
+    app.execute_contract(owner.clone(), earth.clone(), &complete_msg, &[])
+        .unwrap_err();
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + RECOVERY_TIMELOCK_SECONDS + 1, 100);
+    let res = app
+        .execute_contract(owner, earth.clone(), &complete_msg, &[])
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("wasm-passport_recovered")
+            .add_attribute("old_token_id", user.as_str())
+            .add_attribute("new_token_id", new_user.as_str())
+    ));
+
+    // The passport now belongs to the new identity, who can travel with it
+    let query_msg: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: new_user.to_string(),
+    };
+    let passport: NftInfoResponse<Extension> = query(&mut app, nft_contract.clone(), query_msg).unwrap();
+    let metadata = passport.extension.unwrap();
+    assert_eq!(metadata.identity, Some(new_user.clone()));
+    assert_eq!(metadata.dna, Some(example_dna()));
+    let query_msg: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: user.to_string(),
+    };
+    let old_passport: Result<NftInfoResponse<Extension>, _> = query(&mut app, nft_contract, query_msg);
+    assert!(old_passport.is_err());
+
+    let check: JumpRingCheckResponse = query(
+        &mut app,
+        earth.clone(),
+        QueryMsg::JumpRingPreCheck { traveler: new_user.clone() },
+    ).unwrap();
+    assert!(check.valid);
+    let location: TravelerLocationResponse = query(
+        &mut app,
+        earth,
+        QueryMsg::TravelerLocation { traveler: new_user },
+    ).unwrap();
+    assert_eq!(location.location, Some(jupiter));
+}
+
+// The bytes an off-chain attester signs to vouch for `msg` at `portal`
+fn attestation_payload(portal: &Addr, nonce: u64, msg: &MintMsg) -> Vec<u8> {
+    to_vec(&AttestationPayload {
+        portal: portal.clone(),
+        nonce,
+        msg: msg.clone(),
+    }).unwrap()
+}
+
+fn secp256k1_attestation(key: &k256::ecdsa::SigningKey, portal: &Addr, nonce: u64, msg: &MintMsg) -> Attestation {
+    use k256::ecdsa::{signature::Signer, Signature};
+    let signature: Signature = key.sign(&attestation_payload(portal, nonce, msg));
+    Attestation { nonce, signature: Binary::from(signature.as_ref()) }
+}
+
+fn ed25519_attestation(key: &ed25519_zebra::SigningKey, portal: &Addr, nonce: u64, msg: &MintMsg) -> Attestation {
+    let signature = key.sign(&attestation_payload(portal, nonce, msg));
+    Attestation { nonce, signature: Binary::from(<[u8; 64]>::from(signature).to_vec()) }
+}
+
+/// Verifies passports can be minted by anyone relaying a `MintMsg` signed by
+/// the portal's attester, and that each attestation can only be used once
+#[test]
+pub fn minting_attested_passports() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let relayer = Addr::unchecked("relayer");
+    let user = Addr::unchecked("user");
+
+    let earth = create_portal(&mut app, owner.clone(), "earth");
+    let nft_contract = create_cw721(&mut app, &earth);
+    let update_msg = ExecuteMsg::SetPassportContract { contract: nft_contract.clone() };
+    app.execute_contract(owner.clone(), earth.clone(), &update_msg, &[]).unwrap();
+
+    let mint_msg = MintMsg {
+        name: "Traveler Name".to_string(),
+        description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+        image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+        dna: example_dna(),
+        species: "Cyborg".to_string(),
+        sapience_level: SapienceScale::High,
+        identity: user.clone(),
+    };
+    let secp_key = k256::ecdsa::SigningKey::from_bytes(&[7u8; 32]).unwrap();
+    let attested_mint = |attestation: Attestation| ExecuteMsg::MintAttestedPassport {
+        msg: mint_msg.clone(),
+        attestation,
+    };
+
+    // Attested passports can't be minted until an attester is set
+    let msg = attested_mint(secp256k1_attestation(&secp_key, &earth, 0, &mint_msg));
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &msg, &[]).unwrap_err();
+    assert_eq!(ContractError::NoAttester {}, err.downcast().unwrap());
+
+    // Only the owner can set the attester, and only to a well formed key
+    let pubkey = Binary::from(secp_key.verifying_key().to_bytes().to_vec());
+    let set_msg = ExecuteMsg::SetAttester {
+        attester: Some(AttesterKey::Secp256k1 { pubkey: pubkey.clone() }),
+    };
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &set_msg, &[]).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let bad_msg = ExecuteMsg::SetAttester {
+        attester: Some(AttesterKey::Ed25519 { pubkey: pubkey.clone() }),
+    };
+    let err = app.execute_contract(owner.clone(), earth.clone(), &bad_msg, &[]).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidAttesterKey { reason: "ed25519 keys are 32 bytes, not 33".to_string() },
+        err.downcast().unwrap()
+    );
+    app.execute_contract(owner.clone(), earth.clone(), &set_msg, &[]).unwrap();
+    let res: AttesterResponse = query(&mut app, earth.clone(), QueryMsg::Attester {}).unwrap();
+    assert_eq!(res.attester, Some(AttesterKey::Secp256k1 { pubkey }));
+
+    // Attestations for another portal, or with the wrong nonce, are rejected
+    let other_portal = Addr::unchecked("jupiter");
+    let msg = attested_mint(secp256k1_attestation(&secp_key, &other_portal, 0, &mint_msg));
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &msg, &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidAttestation {}, err.downcast().unwrap());
+    let msg = attested_mint(secp256k1_attestation(&secp_key, &earth, 1, &mint_msg));
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &msg, &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidNonce { expected: 0, found: 1 }, err.downcast().unwrap());
+
+    // Tampering with the attested fields invalidates the signature
+    let attestation = secp256k1_attestation(&secp_key, &earth, 0, &mint_msg);
+    let tampered_msg = ExecuteMsg::MintAttestedPassport {
+        msg: MintMsg { sapience_level: SapienceScale::Medium, ..mint_msg.clone() },
+        attestation: attestation.clone(),
+    };
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &tampered_msg, &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidAttestation {}, err.downcast().unwrap());
+
+    // So does truncating it
+    let mut truncated = attestation.clone();
+    truncated.signature = Binary::from(&attestation.signature.as_slice()[..63]);
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &attested_mint(truncated), &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidAttestation {}, err.downcast().unwrap());
+
+    app.execute_contract(relayer.clone(), earth.clone(), &attested_mint(attestation.clone()), &[]).unwrap();
+    let query_msg: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: user.to_string(),
+    };
+    let passport: NftInfoResponse<Extension> = query(&mut app, nft_contract.clone(), query_msg).unwrap();
+    assert_eq!(passport.extension.unwrap().issuer, Some(earth.clone()));
+    let res: AttestationNonceResponse = query(
+        &mut app,
+        earth.clone(),
+        QueryMsg::AttestationNonce { identity: user.clone() },
+    ).unwrap();
+    assert_eq!(res.nonce, 1);
+
+    // The same attestation can't be replayed
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &attested_mint(attestation), &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidNonce { expected: 1, found: 0 }, err.downcast().unwrap());
+
+    // Ed25519 attesters work the same way
+    let ed_key = ed25519_zebra::SigningKey::from([9u8; 32]);
+    let ed_pubkey = <[u8; 32]>::from(ed25519_zebra::VerificationKey::from(&ed_key));
+    let set_msg = ExecuteMsg::SetAttester {
+        attester: Some(AttesterKey::Ed25519 { pubkey: Binary::from(ed_pubkey.to_vec()) }),
+    };
+    app.execute_contract(owner, earth.clone(), &set_msg, &[]).unwrap();
+    let other_msg = MintMsg { identity: Addr::unchecked("other_user"), ..mint_msg };
+    let attestation = ed25519_attestation(&ed_key, &earth, 0, &other_msg);
+    let mut truncated = attestation.clone();
+    truncated.signature = Binary::from(&attestation.signature.as_slice()[..63]);
+    let msg = ExecuteMsg::MintAttestedPassport { msg: other_msg.clone(), attestation: truncated };
+    let err = app.execute_contract(relayer.clone(), earth.clone(), &msg, &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidAttestation {}, err.downcast().unwrap());
+    let msg = ExecuteMsg::MintAttestedPassport { msg: other_msg, attestation };
+    app.execute_contract(relayer, earth, &msg, &[]).unwrap();
+}
+
+/// Verifies the two-step ownership transfer, and that each step 
+/// can only be taken by the right party
+#[test]
+pub fn transferring_ownership() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+    let stranger = Addr::unchecked("stranger");
+    let portal_contract = create_portal(&mut app, owner.clone(), "earth");
+
+    let propose_msg = ExecuteMsg::ProposeNewOwner {
+        new_owner: new_owner.clone(),
+        expiry: None,
+    };
+    let accept_msg = ExecuteMsg::AcceptOwnership {};
+    let cancel_msg = ExecuteMsg::CancelOwnershipProposal {};
+    let renounce_msg = ExecuteMsg::RenounceOwnership {};
+    let rename_msg = ExecuteMsg::SetPlanetName { to: "mars".to_string() };
+
+    // Nothing to accept or cancel before a proposal is made
+    let err = app
+        .execute_contract(new_owner.clone(), portal_contract.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err.downcast().unwrap());
+    let err = app
+        .execute_contract(owner.clone(), portal_contract.clone(), &cancel_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err.downcast().unwrap());
+
+    // Only the owner can propose, and not with an expiry in the past
+    let err = app
+        .execute_contract(stranger.clone(), portal_contract.clone(), &propose_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let current_height = app.block_info().height;
+    let expired_msg = ExecuteMsg::ProposeNewOwner {
+        new_owner: new_owner.clone(),
+        expiry: Some(Expiration::AtHeight(current_height)),
+    };
+    let err = app
+        .execute_contract(owner.clone(), portal_contract.clone(), &expired_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::OwnershipProposalExpired {}, err.downcast().unwrap());
+
+    // Only the owner can cancel a proposal
+    app.execute_contract(owner.clone(), portal_contract.clone(), &propose_msg, &[]).unwrap();
+    let err = app
+        .execute_contract(new_owner.clone(), portal_contract.clone(), &cancel_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(owner.clone(), portal_contract.clone(), &cancel_msg, &[]).unwrap();
+    let err = app
+        .execute_contract(new_owner.clone(), portal_contract.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err.downcast().unwrap());
+
+    // Proposals can't be accepted once expired
+    let expiring_msg = ExecuteMsg::ProposeNewOwner {
+        new_owner: new_owner.clone(),
+        expiry: Some(Expiration::AtHeight(current_height + 10)),
+    };
+    app.execute_contract(owner.clone(), portal_contract.clone(), &expiring_msg, &[]).unwrap();
+    let ownership: OwnershipResponse = query(&mut app, portal_contract.clone(), QueryMsg::Ownership {}).unwrap();
+    assert_eq!(
+        ownership,
+        OwnershipResponse {
+            owner: Some(owner.clone()),
+            pending_owner: Some(new_owner.clone()),
+            pending_expiry: Some(Expiration::AtHeight(current_height + 10)),
+        }
+    );
+    let current_time = get_block_time(&mut app);
+    increment_block_time(&mut app, current_time + 60, 10);
+    let err = app
+        .execute_contract(new_owner.clone(), portal_contract.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::OwnershipProposalExpired {}, err.downcast().unwrap());
+
+    // Only the proposed owner can accept
+    app.execute_contract(owner.clone(), portal_contract.clone(), &propose_msg, &[]).unwrap();
+    let err = app
+        .execute_contract(stranger.clone(), portal_contract.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(new_owner.clone(), portal_contract.clone(), &accept_msg, &[]).unwrap();
+
+    // The previous owner has lost their privileges
+    let err = app
+        .execute_contract(owner.clone(), portal_contract.clone(), &rename_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(new_owner.clone(), portal_contract.clone(), &rename_msg, &[]).unwrap();
+
+    // Only the owner can renounce ownership, after which nobody owns the portal
+    let err = app
+        .execute_contract(owner, portal_contract.clone(), &renounce_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(new_owner.clone(), portal_contract.clone(), &renounce_msg, &[]).unwrap();
+    let ownership: OwnershipResponse = query(&mut app, portal_contract.clone(), QueryMsg::Ownership {}).unwrap();
+    assert_eq!(
+        ownership,
+        OwnershipResponse {
+            owner: None,
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+    let err = app
+        .execute_contract(new_owner, portal_contract, &rename_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+/// Verifies that once a registry is configured, travelers can only 
+/// be sent to (and received from) portals listed in the registry
+#[test]
+pub fn traveling_through_registered_portals() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let user = Addr::unchecked("user");
+
+    let portal_contract = create_portal(&mut app, owner.clone(), "earth");
+    let another_portal = create_portal(&mut app, owner.clone(), "jupiter");
+    let nft_contract = create_cw721(&mut app, &portal_contract);
+    let registry_contract = create_registry(&mut app, &owner);
+
+    // Point both portals at the passport contract and the registry
+    for portal in [&portal_contract, &another_portal] {
+        let update_msg = ExecuteMsg::SetPassportContract {
+            contract: nft_contract.clone(),
+        };
+        app.execute_contract(owner.clone(), portal.clone(), &update_msg, &[]).unwrap();
+        let update_msg = ExecuteMsg::SetRegistryContract {
+            contract: Some(registry_contract.clone()),
+        };
+        app.execute_contract(owner.clone(), portal.clone(), &update_msg, &[]).unwrap();
+    }
+
+    add_passport_minter(&mut app, &nft_contract, &portal_contract, &another_portal);
+
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: example_dna(),
+            species: "Cyborg".to_string(),
+            sapience_level: SapienceScale::High,
+            identity: user.clone(),
+        }
+    };
+    app.execute_contract(potion.clone(), portal_contract.clone(), &mint_msg, &[]).unwrap();
+
+    // Traveling to an unregistered portal fails
+    let travel_msg = ExecuteMsg::JumpRingTravel {
+        to: another_portal.clone(),
+        traveler: user.clone(),
+    };
+    let err = app
+        .execute_contract(potion.clone(), portal_contract.clone(), &travel_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnknownPortal { portal: another_portal.to_string() },
+        err.downcast().unwrap()
+    );
+
+    // Register both portals, without touching either portal's local list
+    for (portal, planet_name) in [(&portal_contract, "earth"), (&another_portal, "jupiter")] {
+        let register_msg = RegistryExecuteMsg::RegisterPortal {
+            portal: portal.to_string(),
+            planet_name: planet_name.to_string(),
+        };
+        app.execute_contract(owner.clone(), registry_contract.clone(), &register_msg, &[]).unwrap();
+    }
+
+    // A rogue JumpRing can't register itself, so it can't land travelers on a registered portal
+    let rogue_portal = create_portal(&mut app, Addr::unchecked("rogue"), "earth");
+    let register_msg = RegistryExecuteMsg::RegisterPortal {
+        portal: rogue_portal.to_string(),
+        planet_name: "earth".to_string(),
+    };
+    let err = app
+        .execute_contract(rogue_portal.clone(), registry_contract.clone(), &register_msg, &[])
+        .unwrap_err();
+    assert_eq!(RegistryContractError::Unauthorized {}, err.downcast().unwrap());
+    let fake_arrival_msg = ExecuteMsg::ArriveFromJumpRing {
+        traveler: user.clone(),
+        origin: rogue_portal.clone(),
+    };
+    let err = app
+        .execute_contract(rogue_portal, another_portal.clone(), &fake_arrival_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    app.execute_contract(potion.clone(), portal_contract.clone(), &travel_msg, &[]).unwrap();
+    let location: TravelerLocationResponse = query(
+        &mut app,
+        another_portal.clone(),
+        QueryMsg::TravelerLocation { traveler: user.clone() },
+    ).unwrap();
+    assert_eq!(location.location, Some(another_portal.clone()));
+
+    // Once deregistered, a portal can no longer be traveled to
+    let deregister_msg = RegistryExecuteMsg::DeregisterPortal {
+        portal: portal_contract.to_string(),
+    };
+    app.execute_contract(owner.clone(), registry_contract, &deregister_msg, &[]).unwrap();
+    let return_msg = ExecuteMsg::JumpRingTravel {
+        to: portal_contract.clone(),
+        traveler: user,
+    };
+    let err = app
+        .execute_contract(potion, another_portal, &return_msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::UnknownPortal { portal: portal_contract.to_string() },
+        err.downcast().unwrap()
+    );
+}
+
+/// Verifies that pre-checks are based on the passport stored in the 
+/// passport contract, and that every failing requirement is reported
+#[test]
+pub fn checking_jump_ring_requirements() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let potion = Addr::unchecked("potion");
+    let cyborg = Addr::unchecked("cyborg");
+    let human = Addr::unchecked("human");
+    let stranger = Addr::unchecked("stranger");
+
+    let portal_contract = create_portal(
+        &mut app,
+        owner.clone(),
+        "earth",
+    );
+    let nft_contract = create_cw721(&mut app, &portal_contract);
+    let update_msg = ExecuteMsg::SetPassportContract {
+        contract: nft_contract,
+    };
+    app.execute_contract(
+        owner.clone(), 
+        portal_contract.clone(), 
+        &update_msg, 
+        &[]
+    )
+    .unwrap();
+
+    // A sapient cyborg and a not-so-sapient human
+    let travelers = vec![
+        (cyborg.clone(), "Cyborg", SapienceScale::High, example_dna()),
+        (human.clone(), "Human", SapienceScale::Medium, example_dna()),
+    ];
+    for (identity, species, sapience_level, dna) in travelers {
+        let mint_msg = ExecuteMsg::MintPassport {
+            msg: MintMsg {
+                name: "Traveler Name".to_string(),
+                description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+                image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+                dna,
+                species: species.to_string(),
+                sapience_level,
+                identity,
+            }
+        };
+        app.execute_contract(
+            potion.clone(), 
+            portal_contract.clone(), 
+            &mint_msg, 
+            &[]
+        )
+        .unwrap();
+    }
+
+    // Cyborg meets every requirement
+    let check: JumpRingCheckResponse = query(
+        &mut app, 
+        portal_contract.clone(), 
+        QueryMsg::JumpRingPreCheck { traveler: cyborg },
+    ).unwrap();
+    assert_eq!(
+        check,
+        JumpRingCheckResponse {
+            valid: true,
+            failed_requirements: vec![],
+        }
+    );
+
+    // Passports can't be minted without valid cyborg DNA
+    let mint_msg = ExecuteMsg::MintPassport {
+        msg: MintMsg {
+            name: "Traveler Name".to_string(),
+            description: "Not a cyborg".to_string(),
+            image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+            dna: "".to_string(),
+            species: "Human".to_string(),
+            sapience_level: SapienceScale::Medium,
+            identity: stranger.clone(),
+        }
+    };
+    let err = app
+        .execute_contract(potion.clone(), portal_contract.clone(), &mint_msg, &[])
+        .unwrap_err();
+    assert!(matches!(err.downcast().unwrap(), ContractError::InvalidDna { .. }));
+
+    // Human fails the sapience requirement
+    let check: JumpRingCheckResponse = query(
+        &mut app, 
+        portal_contract.clone(), 
+        QueryMsg::JumpRingPreCheck { traveler: human },
+    ).unwrap();
+    assert_eq!(
+        check,
+        JumpRingCheckResponse {
+            valid: false,
+            failed_requirements: vec![
+                FailedRequirement::InsufficientSapience {
+                    required: SapienceScale::High,
+                    actual: Some(SapienceScale::Medium),
+                },
+            ],
+        }
+    );
+
+    // Travelers without a passport can't be checked any further
+    let check: JumpRingCheckResponse = query(
+        &mut app, 
+        portal_contract, 
+        QueryMsg::JumpRingPreCheck { traveler: stranger },
+    ).unwrap();
+    assert_eq!(
+        check,
+        JumpRingCheckResponse {
+            valid: false,
+            failed_requirements: vec![FailedRequirement::NoPassport {}],
+        }
+    );
+}
+
+#[test]
+pub fn migrating_portal() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let sapients = vec![
+        Sapient {
+            name: "Some Cyborg".to_string(),
+            species: Species {
+                name: "Cyborg".to_string(),
+                sapience_level: SapienceScale::High,
+            },
+            telepathic: true,
+        },
+    ];
+
+    // Course 01 portal config, as saved by cosmwasm_storage::singleton
+    let legacy = LegacyConfig {
+        owner: Addr::unchecked("owner"),
+        planet_name: "earth".to_string(),
+        planet_sapients: vec![LegacySapient {
+            name: "Some Cyborg".to_string(),
+            telepathic: true,
+        }],
+        minimum_sapience: SapienceScale::Medium,
+    };
+    deps.storage.set(&legacy_key(b"config"), &to_vec(&legacy).unwrap());
+
+    // Course 02 upgrades need a stored contract version
+    let err = portal_migrate(deps.as_mut(), env.clone(), MigrateMsg::Upgrade {}).unwrap_err();
+    assert!(matches!(err, ContractError::Std(StdError::NotFound { .. })));
+
+    let migrate_msg = MigrateMsg::FromLegacy {
+        planet_sapients: sapients.clone(),
+        passport_contract: Addr::unchecked("passport"),
+        potion_contract: Addr::unchecked("potion"),
+        registry_contract: None,
+        jump_ring_fee: None,
+    };
+    portal_migrate(deps.as_mut(), env.clone(), migrate_msg.clone()).unwrap();
+
+    let config = CONFIG.load(&deps.storage).unwrap();
+    assert_eq!(config.owner, Some(Addr::unchecked("owner")));
+    assert_eq!(config.planet_name, "earth".to_string());
+    assert_eq!(config.planet_sapients, sapients);
+    assert_eq!(config.minimum_sapience, SapienceScale::Medium);
+    assert_eq!(config.passport_contract, Addr::unchecked("passport"));
+    assert_eq!(config.potion_contract, Addr::unchecked("potion"));
+    assert_eq!(deps.storage.get(&legacy_key(b"config")), None);
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.contract, "crates.io:portal".to_string());
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION").to_string());
+
+    // The legacy config is gone, so it can't be migrated twice
+    let err = portal_migrate(deps.as_mut(), env.clone(), migrate_msg).unwrap_err();
+    assert_eq!(err, ContractError::NoLegacyConfig {});
+
+    // Upgrading from an older version works
+    set_contract_version(&mut deps.storage, "crates.io:portal", "0.0.1").unwrap();
+    portal_migrate(deps.as_mut(), env.clone(), MigrateMsg::Upgrade {}).unwrap();
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION").to_string());
+
+    // Downgrading doesn't
+    set_contract_version(&mut deps.storage, "crates.io:portal", "99.0.0").unwrap();
+    let err = portal_migrate(deps.as_mut(), env.clone(), MigrateMsg::Upgrade {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Migrate(MigrateError::CannotDowngrade {
+            stored: "99.0.0".to_string(),
+            current: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    );
+
+    // Neither does migrating some other contract
+    set_contract_version(&mut deps.storage, "crates.io:potion", "0.0.1").unwrap();
+    let err = portal_migrate(deps.as_mut(), env, MigrateMsg::Upgrade {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Migrate(MigrateError::WrongContract {
+            expected: "crates.io:portal".to_string(),
+            found: "crates.io:potion".to_string(),
+        })
+    );
+}
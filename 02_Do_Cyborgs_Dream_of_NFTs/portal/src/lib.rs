@@ -1,6 +1,7 @@
 pub mod contract;
 pub mod error;
 pub mod execute_fns;
+pub mod migrate_fns;
 pub mod msg;
 pub mod query_fns;
 pub mod state;
@@ -0,0 +1,90 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::{from_slice, Addr, Coin, DepsMut, Response, Storage};
+use cw2::{get_contract_version, set_contract_version};
+
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG};
+use universe::migrate::check_upgrade;
+use universe::species::{SapienceScale, Sapient};
+
+// Course 01 kept its config in a `cosmwasm_storage` singleton
+static LEGACY_CONFIG_KEY: &[u8] = b"config";
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct LegacySapient {
+    pub name: String,
+    pub telepathic: bool,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct LegacyConfig {
+    pub owner: Addr,
+    pub planet_name: String,
+    pub planet_sapients: Vec<LegacySapient>,
+    pub minimum_sapience: SapienceScale,
+}
+
+pub fn upgrade(
+    contract_name: &str,
+    contract_version: &str,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    check_upgrade(&stored.contract, &stored.version, contract_name, contract_version)?;
+
+    set_contract_version(deps.storage, contract_name, contract_version)?;
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", contract_version))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn migrate_legacy_config(
+    contract_name: &str,
+    contract_version: &str,
+    planet_sapients: Vec<Sapient>,
+    passport_contract: Addr,
+    potion_contract: Addr,
+    registry_contract: Option<Addr>,
+    jump_ring_fee: Option<Coin>,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    let key = legacy_key(LEGACY_CONFIG_KEY);
+    let legacy: LegacyConfig = match deps.storage.get(&key) {
+        Some(data) => from_slice(&data)?,
+        None => return Err(ContractError::NoLegacyConfig {}),
+    };
+
+    let config = Config {
+        owner: Some(legacy.owner),
+        planet_name: legacy.planet_name,
+        planet_sapients,
+        minimum_sapience: legacy.minimum_sapience,
+        passport_contract,
+        potion_contract,
+        registry_contract,
+        jump_ring_fee,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    remove_legacy_config(deps.storage);
+
+    set_contract_version(deps.storage, contract_name, contract_version)?;
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", "legacy")
+        .add_attribute("to_version", contract_version))
+}
+
+fn remove_legacy_config(storage: &mut dyn Storage) {
+    storage.remove(&legacy_key(LEGACY_CONFIG_KEY));
+}
+
+/// Storage key `cosmwasm_storage` singletons were saved under
+/// (the namespace, prefixed by its length as a big-endian u16)
+pub fn legacy_key(namespace: &[u8]) -> Vec<u8> {
+    let mut key = (namespace.len() as u16).to_be_bytes().to_vec();
+    key.extend_from_slice(namespace);
+    key
+}
@@ -1,13 +1,26 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::Addr;
-use universe::species::{SapienceScale, Sapient, Traveler};
+use cosmwasm_std::{Addr, Binary, Coin};
+use cw_utils::Expiration;
+use universe::species::{SapienceScale, Sapient};
+
+use crate::state::{AttesterKey, PauseInfo, TravelRecord};
 
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum QueryMsg {
-    JumpRingPreCheck { traveler: Traveler },
+    JumpRingPreCheck { traveler: Addr },
     MinimumSapience {},
+    KnownPortals {},
+    TravelerLocation { traveler: Addr },
+    TravelHistory { traveler: Addr, start_after: Option<u64>, limit: Option<u32> },
+    RecentTravelers { start_after: Option<u64>, limit: Option<u32> },
+    JumpRingFee {},
+    CollectedFees {},
+    Ownership {},
+    Status {},
+    Attester {},
+    AttestationNonce { identity: Addr },
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -18,8 +31,27 @@ pub enum ExecuteMsg {
     SetMinimumSapience { to: SapienceScale },
     SetPassportContract { contract: Addr },
     SetPotionContract { contract: Addr },
+    SetKnownPortals { to: Vec<Addr> },
+    SetRegistryContract { contract: Option<Addr> },
+    SetTravelLogLimit { to: u64 },
+    SetJumpRingFee { to: Option<Coin> },
+    WithdrawFees { to: Addr, amount: Coin },
+    ProposeNewOwner { new_owner: Addr, expiry: Option<Expiration> },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
+    RenounceOwnership {},
+    SetGuardians { guardians: Vec<Addr> },
+    Pause { reason: String },
+    Unpause {},
+    SetAttester { attester: Option<AttesterKey> },
     MintPassport { msg: MintMsg },
+    MintAttestedPassport { msg: MintMsg, attestation: Attestation },
+    RenewPassport { msg: RenewMsg },
+    RevokePassport { traveler: Addr, reason: String },
+    RecoverPassport { traveler: Addr, new_identity: Addr },
+    CompletePassportRecovery { traveler: Addr, new_identity: Addr },
     JumpRingTravel { to: Addr, traveler: Addr, },
+    ArriveFromJumpRing { traveler: Addr, origin: Addr },
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -30,6 +62,24 @@ pub struct InstantiateMsg {
     pub minimum_sapience: SapienceScale,
     pub passport_contract: Addr,
     pub potion_contract: Addr,
+    pub registry_contract: Option<Addr>,
+    pub jump_ring_fee: Option<Coin>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum MigrateMsg {
+    // Upgrade a course 02 portal to this version
+    Upgrade {},
+    // Upgrade a course 01 portal. Course 01 sapients don't record
+    // their species, so the planet's sapients must be restated
+    FromLegacy {
+        planet_sapients: Vec<Sapient>,
+        passport_contract: Addr,
+        potion_contract: Addr,
+        registry_contract: Option<Addr>,
+        jump_ring_fee: Option<Coin>,
+    },
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -44,7 +94,121 @@ pub struct MintMsg {
     pub identity: Addr,         // The owner's wallet address
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// Proof the configured attester vouches for a `MintMsg`
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Attestation {
+    pub nonce: u64,             // Must match the `AttestationNonce` of the passport's identity
+    pub signature: Binary,
+}
+
+/// What the attester signs: the canonical JSON encoding of this struct
+/// (fields in declaration order, no whitespace). Ed25519 signatures are over
+/// those bytes, and secp256k1 signatures are over their sha256 digest.
+/// Naming the portal keeps an attestation from being replayed at another JumpRing
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttestationPayload {
+    pub portal: Addr,
+    pub nonce: u64,
+    pub msg: MintMsg,
+}
+
+/// Passport details the issuing JumpRing can correct. Fields left unset
+/// are unchanged, and a passport's DNA and proof of identity are never changed
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RenewMsg {
+    pub traveler: Addr,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub species: Option<String>,
+    pub sapience_level: Option<SapienceScale>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct JumpRingCheckResponse {
     pub valid: bool,
+    pub failed_requirements: Vec<FailedRequirement>,
+}
+
+/// Every JumpRing requirement a traveler's passport doesn't currently meet
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum FailedRequirement {
+    NoPassport {},
+    IdentityMismatch {},
+    InsufficientSapience { required: SapienceScale, actual: Option<SapienceScale> },
+    NotACyborg {},
+    PassportRevoked { reason: String },
+    PassportExpired {},
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct KnownPortalsResponse {
+    pub portals: Vec<Addr>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TravelerLocationResponse {
+    pub traveler: Addr,
+    pub location: Option<Addr>,     // Address of the portal the traveler last passed through
+}
+
+/// Travel log entries, newest first
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TravelLogResponse {
+    pub entries: Vec<TravelLogEntry>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TravelLogEntry {
+    pub id: u64,                    // Pass as `start_after` to fetch the next page
+    pub record: TravelRecord,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JumpRingFeeResponse {
+    pub fee: Option<Coin>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CollectedFeesResponse {
+    pub fees: Vec<Coin>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatusResponse {
+    pub paused: Option<PauseInfo>,  // Who paused the JumpRing, when and why
+    pub guardians: Vec<Addr>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttesterResponse {
+    pub attester: Option<AttesterKey>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AttestationNonceResponse {
+    pub identity: Addr,
+    pub nonce: u64,             // Nonce the next attestation for this identity must carry
 }
@@ -1,11 +1,24 @@
 
 use cosmwasm_std::{
-    to_binary, Binary, Deps, StdResult,
+    to_binary, Addr, Binary, Coin, Deps, Order, QueryRequest, StdResult, WasmQuery,
 };
+use cw_storage_plus::Bound;
+use cw721::{NftInfoResponse, TokenValidity};
+use passport_token::{Extension, QueryMsg as Cw721QueryMsg};
+
 use crate::{
-    msg::JumpRingCheckResponse, state::CONFIG,
+    msg::{
+        AttestationNonceResponse, AttesterResponse, CollectedFeesResponse, FailedRequirement, 
+        JumpRingCheckResponse, JumpRingFeeResponse, KnownPortalsResponse, OwnershipResponse, 
+        StatusResponse, TravelLogEntry, TravelLogResponse, TravelerLocationResponse,
+    }, 
+    state::{
+        travel_log, ATTESTATION_NONCES, ATTESTER, COLLECTED_FEES, CONFIG, GUARDIANS, KNOWN_PORTALS, 
+        PAUSED, PENDING_OWNER, TRAVELER_LOCATIONS,
+    },
 };
-use universe::species::{SapienceResponse, Traveler};
+use universe::dna::CyborgDna;
+use universe::species::SapienceResponse;
 
 pub fn minimum_sapience(deps: Deps) -> StdResult<Binary> {
     let config = CONFIG.load(deps.storage)?;
@@ -15,13 +28,160 @@ pub fn minimum_sapience(deps: Deps) -> StdResult<Binary> {
     Ok(out)
 }
 
-// XXX TODO: This is currently invalid / insecure; takes 
-// a Traveler as an argument instead of loading a storage
-// item keyed by a Traveler address. This Query cannot 
-// guarantee its correctness
-pub fn jump_ring_check(traveler: Traveler) -> StdResult<Binary> {
+pub fn known_portals(deps: Deps) -> StdResult<Binary> {
+    let portals = KNOWN_PORTALS.may_load(deps.storage)?.unwrap_or_default();
+    to_binary(&KnownPortalsResponse { portals })
+}
+
+pub fn traveler_location(traveler: Addr, deps: Deps) -> StdResult<Binary> {
+    let location = TRAVELER_LOCATIONS.may_load(deps.storage, &traveler)?;
+    to_binary(&TravelerLocationResponse { traveler, location })
+}
+
+pub fn ownership(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    let pending = PENDING_OWNER.may_load(deps.storage)?;
+    to_binary(&OwnershipResponse {
+        owner: config.owner,
+        pending_owner: pending.as_ref().map(|p| p.new_owner.clone()),
+        pending_expiry: pending.and_then(|p| p.expiry),
+    })
+}
+
+pub fn status(deps: Deps) -> StdResult<Binary> {
+    to_binary(&StatusResponse {
+        paused: PAUSED.may_load(deps.storage)?,
+        guardians: GUARDIANS.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
+pub fn attester(deps: Deps) -> StdResult<Binary> {
+    to_binary(&AttesterResponse {
+        attester: ATTESTER.may_load(deps.storage)?,
+    })
+}
+
+pub fn attestation_nonce(identity: Addr, deps: Deps) -> StdResult<Binary> {
+    let nonce = ATTESTATION_NONCES.may_load(deps.storage, &identity)?.unwrap_or_default();
+    to_binary(&AttestationNonceResponse { identity, nonce })
+}
+
+pub fn jump_ring_fee(deps: Deps) -> StdResult<Binary> {
+    let config = CONFIG.load(deps.storage)?;
+    to_binary(&JumpRingFeeResponse {
+        fee: config.jump_ring_fee,
+    })
+}
+
+pub fn collected_fees(deps: Deps) -> StdResult<Binary> {
+    let fees = COLLECTED_FEES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect::<StdResult<Vec<_>>>()?;
+    to_binary(&CollectedFeesResponse { fees })
+}
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+pub fn travel_history(
+    traveler: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    deps: Deps,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    let entries = travel_log()
+        .idx
+        .traveler
+        .prefix(traveler)
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(id, record)| TravelLogEntry { id, record }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&TravelLogResponse { entries })
+}
+
+pub fn recent_travelers(
+    start_after: Option<u64>,
+    limit: Option<u32>,
+    deps: Deps,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let end = start_after.map(Bound::exclusive);
+
+    let entries = travel_log()
+        .range(deps.storage, None, end, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(id, record)| TravelLogEntry { id, record }))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&TravelLogResponse { entries })
+}
+
+pub fn jump_ring_check(traveler: Addr, deps: Deps) -> StdResult<Binary> {
+    let failed_requirements = check_travel_requirements(&traveler, deps)?;
     let out = to_binary(&JumpRingCheckResponse {
-        valid: traveler.cyberdized,
+        valid: failed_requirements.is_empty(),
+        failed_requirements,
     })?;
     Ok(out)
 }
+
+/// Loads the traveler's passport from the passport contract and 
+/// returns every JumpRing requirement it fails to meet
+pub fn check_travel_requirements(
+    traveler: &Addr, 
+    deps: Deps,
+) -> StdResult<Vec<FailedRequirement>> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Passports are keyed by the traveler's address
+    let query_msg: passport_token::QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: traveler.clone().into(),
+    };
+    let query_req = QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.passport_contract.to_string(),
+        msg: to_binary(&query_msg)?,
+    });
+    let (passport, validity) = match deps.querier.query::<NftInfoResponse<Extension>>(&query_req) {
+        Ok(NftInfoResponse { extension: Some(metadata), validity, .. }) => (metadata, validity),
+        _ => return Ok(vec![FailedRequirement::NoPassport {}]),
+    };
+
+    let mut failed = vec![];
+    match validity {
+        TokenValidity::Valid {} => {}
+        TokenValidity::Revoked { reason } => failed.push(FailedRequirement::PassportRevoked { reason }),
+        TokenValidity::Expired { .. } => failed.push(FailedRequirement::PassportExpired {}),
+    }
+    if passport.identity.as_ref() != Some(traveler) {
+        failed.push(FailedRequirement::IdentityMismatch {});
+    }
+
+    let sapient_enough = passport
+        .sapience_level
+        .as_ref()
+        .map(|level| level.as_num() >= config.minimum_sapience.as_num())
+        .unwrap_or(false);
+    if !sapient_enough {
+        failed.push(FailedRequirement::InsufficientSapience {
+            required: config.minimum_sapience,
+            actual: passport.sapience_level,
+        });
+    }
+
+    // Only cyborgs have (valid) DNA recorded on their passports
+    let is_cyborg = passport
+        .dna
+        .map(|dna| CyborgDna::validate(&dna).is_ok())
+        .unwrap_or(false);
+    if !is_cyborg {
+        failed.push(FailedRequirement::NotACyborg {});
+    }
+
+    Ok(failed)
+}
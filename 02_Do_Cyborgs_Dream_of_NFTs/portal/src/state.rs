@@ -1,17 +1,125 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item};
+use cosmwasm_std::{Addr, Binary, Coin, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
 use universe::species::{SapienceScale, Sapient};
 
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct Config {
-    pub owner: Addr,
+    pub owner: Option<Addr>,               // None once ownership has been renounced
     pub planet_name: String,
     pub planet_sapients: Vec<Sapient>,
     pub minimum_sapience: SapienceScale,
     pub passport_contract: Addr,
     pub potion_contract: Addr,
+    pub registry_contract: Option<Addr>,    // When set, travel is restricted to portals in the JumpRing registry
+    pub jump_ring_fee: Option<Coin>,        // Charged for each trip through this portal, when set
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
\ No newline at end of file
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct PendingOwner {
+    pub new_owner: Addr,
+    pub expiry: Option<Expiration>,     // Proposal can't be accepted after this
+}
+
+// Ownership transfer awaiting acceptance by the proposed new owner
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+
+// Addresses, besides the owner, who can pause the JumpRing in an emergency.
+// Only the owner can unpause it
+pub const GUARDIANS: Item<Vec<Addr>> = Item::new("guardians");
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseInfo {
+    pub paused_by: Addr,
+    pub reason: String,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+// Travel and passport minting are halted while this is set
+pub const PAUSED: Item<PauseInfo> = Item::new("paused");
+
+/// Public key of an off-chain service trusted to vouch for
+/// travelers, and the scheme its attestations are signed with
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum AttesterKey {
+    Secp256k1 { pubkey: Binary },   // Compressed (33 byte) or uncompressed (65 byte) key
+    Ed25519 { pubkey: Binary },
+}
+
+// When set, anyone can mint a passport by relaying a
+// `MintMsg` signed by this key, not just the potion contract
+pub const ATTESTER: Item<AttesterKey> = Item::new("attester");
+
+// Nonce the next attested passport minted for each identity must carry,
+// so a signed `MintMsg` can't be replayed after its passport is burned
+pub const ATTESTATION_NONCES: Map<&Addr, u64> = Map::new("attestation_nonces");
+
+// Portals this JumpRing is allowed to send travelers to, and receive them from
+pub const KNOWN_PORTALS: Item<Vec<Addr>> = Item::new("known_portals");
+
+// Fees collected from travelers, by denom, that haven't been withdrawn yet
+pub const COLLECTED_FEES: Map<&str, Uint128> = Map::new("collected_fees");
+
+// Maps a traveler's address to the portal they're currently located at
+pub const TRAVELER_LOCATIONS: Map<&Addr, Addr> = Map::new("traveler_locations");
+
+// Travel log entries are kept until this many newer entries have been recorded
+pub const DEFAULT_TRAVEL_LOG_LIMIT: u64 = 1000;
+
+pub const TRAVEL_LOG_LIMIT: Item<u64> = Item::new("travel_log_limit");
+
+// Most travel log entries dropped by one transaction. Entries left over
+// the limit after a big drop are pruned by later travel, or limit updates
+pub const MAX_TRAVEL_LOG_PRUNE: u64 = 100;
+
+// Id of the next travel log entry, and of the oldest one still retained
+pub const TRAVEL_LOG_NEXT_ID: Item<u64> = Item::new("travel_log_next_id");
+pub const TRAVEL_LOG_OLDEST_ID: Item<u64> = Item::new("travel_log_oldest_id");
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum TravelDirection {
+    Arrival,
+    Departure,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TravelRecord {
+    pub traveler: Addr,
+    pub direction: TravelDirection,
+    pub portal: Addr,       // Origin portal of an arrival, or destination portal of a departure
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+pub struct TravelLogIndexes<'a> {
+    pub traveler: MultiIndex<'a, Addr, TravelRecord, u64>,
+}
+
+impl<'a> IndexList<TravelRecord> for TravelLogIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<TravelRecord>> + '_> {
+        let v: Vec<&dyn Index<TravelRecord>> = vec![&self.traveler];
+        Box::new(v.into_iter())
+    }
+}
+
+// Arrivals and departures through this portal, keyed by 
+// sequential id and indexed by traveler
+pub fn travel_log<'a>() -> IndexedMap<'a, u64, TravelRecord, TravelLogIndexes<'a>> {
+    let indexes = TravelLogIndexes {
+        traveler: MultiIndex::new(
+            |_pk, d: &TravelRecord| d.traveler.clone(),
+            "travel_log",
+            "travel_log__traveler",
+        ),
+    };
+    IndexedMap::new("travel_log", indexes)
+}
@@ -0,0 +1,26 @@
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use potion::msg::{
+    AddressSwigsResponse, ExecuteMsg, ImbiberResponse, ImbibersResponse, InstantiateMsg,
+    MigrateMsg, QueryMsg, SwigResponse,
+};
+use potion::state::State;
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
+    export_schema(&schema_for!(State), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(SwigResponse), &out_dir);
+    export_schema(&schema_for!(AddressSwigsResponse), &out_dir);
+    export_schema(&schema_for!(ImbiberResponse), &out_dir);
+    export_schema(&schema_for!(ImbibersResponse), &out_dir);
+}
@@ -0,0 +1,132 @@
+use crate::error::ContractError;
+use crate::execute_fns::{
+    cyborg_dna_generator::MAX_DNA_LENGTH,
+    imbibe_potion::imbibe_potion,
+    ownership::{accept_ownership, cancel_ownership_proposal, propose_new_owner, renounce_ownership},
+    reindex_imbibers::reindex_imbibers,
+    step_through_jumpring::step_through_jumpring,
+    swigs::{refill, set_swig_limit, set_swig_price, withdraw_funds},
+};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::query_fns::{
+    imbiber, imbibers, imbibers_by_species, number_of_swigs, ownership, swigs_for,
+};
+use crate::state::{State, CONFIG};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsgResult,
+};
+use cw2::{get_contract_version, set_contract_version};
+use universe::migrate::check_upgrade;
+
+// Version info for migration
+const CONTRACT_NAME: &str = "crates.io:potion";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+static DEFAULT_NUMBER_OF_SWIGS: u64 = 3;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(_deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.result {
+        SubMsgResult::Ok(_) => Ok(Response::default()),
+        SubMsgResult::Err(_) => Err(ContractError::NothingToSeeHere {}),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::NumberOfSwigs {} => to_binary(&number_of_swigs(deps)?),
+        QueryMsg::SwigsFor { address } => to_binary(&swigs_for(deps, address)?),
+        QueryMsg::Imbiber { address } => to_binary(&imbiber(deps, address)?),
+        QueryMsg::Imbibers { start_after, limit } => {
+            to_binary(&imbibers(deps, start_after, limit)?)
+        }
+        QueryMsg::ImbibersBySpecies {
+            species,
+            start_after,
+            limit,
+        } => to_binary(&imbibers_by_species(deps, species, start_after, limit)?),
+        QueryMsg::Ownership {} => to_binary(&ownership(deps)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ImbibePotion {
+            name,
+            species,
+            description,
+            image,
+        } => imbibe_potion(name, species, description, image, deps, info),
+        ExecuteMsg::StepThroughJumpRing {
+            portal,
+            destination,
+        } => step_through_jumpring(portal, destination, deps, info),
+        ExecuteMsg::ProposeNewOwner { new_owner, expiry } => {
+            propose_new_owner(new_owner, expiry, deps, env, info)
+        }
+        ExecuteMsg::AcceptOwnership {} => accept_ownership(deps, env, info),
+        ExecuteMsg::CancelOwnershipProposal {} => cancel_ownership_proposal(deps, info),
+        ExecuteMsg::RenounceOwnership {} => renounce_ownership(deps, info),
+        ExecuteMsg::Refill { amount } => refill(amount, deps, info),
+        ExecuteMsg::SetSwigPrice { price } => set_swig_price(price, deps, info),
+        ExecuteMsg::SetSwigLimit { limit } => set_swig_limit(limit, deps, info),
+        ExecuteMsg::WithdrawFunds { to, amount } => withdraw_funds(to, amount, deps, info),
+        ExecuteMsg::ReindexImbibers { start_after, limit } => {
+            reindex_imbibers(start_after, limit, deps, info)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // Out of range values would make every imbibing fail
+    if msg.dna_length == 0 || msg.dna_length > MAX_DNA_LENGTH {
+        return Err(ContractError::InvalidDnaLength { length: msg.dna_length });
+    }
+    if msg.dna_modulus == 0 {
+        return Err(ContractError::InvalidDnaModulus {});
+    }
+
+    let state = State {
+        owner: Some(msg.owner),
+        dna_length: msg.dna_length,
+        dna_modulus: msg.dna_modulus,
+        swigs: msg.swigs.unwrap_or(DEFAULT_NUMBER_OF_SWIGS),
+        swig_price: msg.swig_price,
+        swig_limit: msg.swig_limit,
+        portal_contract: msg.portal_contract,
+        section31_contract: msg.section31_contract,
+    };
+    CONFIG.save(deps.storage, &state)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    check_upgrade(&stored.contract, &stored.version, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    // Imbibers saved before they were indexed by species are indexed
+    // afterwards, a page at a time, with `ExecuteMsg::ReindexImbibers`
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
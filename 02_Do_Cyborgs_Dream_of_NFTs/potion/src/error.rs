@@ -0,0 +1,34 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+use universe::migrate::MigrateError;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Keep your eyes to yourself, citizen.")]
+    NothingToSeeHere {},
+    #[error("You're sipping at an empty flask, my friend.")]
+    OutOfSwigs {},
+    #[error("You've had your {limit}, pal. Time to go home.")]
+    SwigLimitReached { limit: u64 },
+    #[error("That'll be {required}, and not a micro less.")]
+    InsufficientPayment { required: String },
+    #[error("There's no way you'd make it through the ring!")]
+    NotACyborg {},
+    #[error("You think you're so smart, don't ya?")]
+    NotSapientEnough {},
+    #[error("Unauthorized. This is not the cyborg you're looking for.")]
+    Unauthorized {},
+    #[error("Nobody's been offered the flask.")]
+    NoPendingOwner {},
+    #[error("That offer has gone stale.")]
+    OwnershipProposalExpired {},
+    #[error("A cyborg with {length} genes? Not in this universe.")]
+    InvalidDnaLength { length: usize },
+    #[error("Every gene needs at least one variant.")]
+    InvalidDnaModulus {},
+    #[error("{0}")]
+    Migrate(#[from] MigrateError),
+}
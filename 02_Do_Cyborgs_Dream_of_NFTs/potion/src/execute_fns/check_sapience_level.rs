@@ -0,0 +1,26 @@
+use crate::error::ContractError;
+use crate::state::Imbiber;
+use cosmwasm_std::{to_binary, Addr, DepsMut, QueryRequest, Response, WasmQuery};
+use portal::msg::QueryMsg;
+use universe::species::SapienceResponse;
+use universe::utils::sapience_value;
+
+pub fn check_sapience_level(
+    portal: &Addr,
+    deps: &DepsMut,
+    imbiber: &Imbiber,
+) -> Result<Response, ContractError> {
+    let query = WasmQuery::Smart {
+        contract_addr: portal.to_string(),
+        msg: to_binary(&QueryMsg::MinimumSapience {})?,
+    };
+    let res: SapienceResponse = deps.querier.query(&QueryRequest::Wasm(query))?;
+
+    let species_sapience = &imbiber.species.sapience_level;
+
+    // Imbibers must be at least as sapient as the portal requires
+    if sapience_value(species_sapience) < sapience_value(&res.level) {
+        return Err(ContractError::NotSapientEnough {});
+    };
+    Ok(Response::default())
+}
@@ -0,0 +1,27 @@
+use crate::error::ContractError;
+use cosmwasm_std::Coin;
+
+pub fn check_sent_required_payment(
+    sent: &[Coin],
+    required: Option<Coin>,
+) -> Result<(), ContractError> {
+    if let Some(required_coin) = required {
+        let required_amount = required_coin.amount.u128();
+        if required_amount > 0 {
+            let sent_sufficient_funds = sent.iter().any(|coin| {
+                // check if a given sent coin matches denom
+                // and has sufficient amount
+                coin.denom == required_coin.denom && coin.amount.u128() >= required_amount
+            });
+
+            if sent_sufficient_funds {
+                return Ok(());
+            } else {
+                return Err(ContractError::InsufficientPayment {
+                    required: required_coin.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
\ No newline at end of file
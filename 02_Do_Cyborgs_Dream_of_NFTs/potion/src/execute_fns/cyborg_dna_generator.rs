@@ -0,0 +1,19 @@
+use sha3::{Digest, Keccak256};
+
+// Genes are taken from a Keccak256 hash, so there can't be more of them than it has bytes
+pub const MAX_DNA_LENGTH: usize = 32;
+
+pub fn cyborg_dna_generator(value: &str, dna_length: usize, dna_modulus: u8) -> Vec<u8> {
+    let mut hasher = Keccak256::new();
+    hasher.update(value);
+
+    let result = hasher.finalize();
+    let slice = &result[0..dna_length];
+    let mut truncated = Vec::with_capacity(dna_length);
+
+    for item in slice {
+        truncated.push(item % dna_modulus);
+    }
+
+    truncated
+}
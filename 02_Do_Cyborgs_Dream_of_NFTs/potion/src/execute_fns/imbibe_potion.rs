@@ -0,0 +1,94 @@
+use crate::error::ContractError;
+use crate::execute_fns::check_sent_required_payment::check_sent_required_payment;
+use crate::execute_fns::cyborg_dna_generator::cyborg_dna_generator;
+use crate::state::{imbibers, Imbiber, CONFIG, SWIGS_IMBIBED};
+use cosmwasm_std::{to_binary, CosmosMsg, DepsMut, MessageInfo, Response, SubMsg, WasmMsg};
+use portal::msg::{ExecuteMsg as PortalExecuteMsg, MintMsg};
+use universe::dna::CyborgDna;
+use universe::section31::ExecuteMsg as Section31Execute;
+use universe::species::Species;
+
+pub fn imbibe_potion(
+    name: String,
+    species: Species,
+    description: String,
+    image: String,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut state = CONFIG.load(deps.storage)?;
+    let swigs = state.swigs;
+    if swigs == 0 {
+        return Err(ContractError::OutOfSwigs {});
+    }
+    let imbibed = SWIGS_IMBIBED
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if let Some(limit) = state.swig_limit {
+        if imbibed >= limit {
+            return Err(ContractError::SwigLimitReached { limit });
+        }
+    }
+    check_sent_required_payment(&info.funds, state.swig_price.clone())?;
+
+    state.swigs = swigs - 1;
+    CONFIG.save(deps.storage, &state)?;
+    SWIGS_IMBIBED.save(deps.storage, &info.sender, &(imbibed + 1))?;
+
+    let mut res = Response::new()
+        .add_attribute("action", "imbibe_potion")
+        .add_attribute("imbiber", info.sender.clone());
+
+    // Imbibers are turned into cyborgs, and issued a passport, by their first
+    // swig. Later swigs, up to the swig limit, leave both as they are, since
+    // the JumpRing won't mint a second passport for the same identity
+    if !imbibers().has(deps.storage, &info.sender) {
+        let cyborg_dna = CyborgDna::new(cyborg_dna_generator(
+            info.sender.as_str(),
+            state.dna_length,
+            state.dna_modulus,
+        ));
+
+        let cyborg = Imbiber {
+            address: info.sender.clone(),
+            species: species.clone(),
+            name: name.clone(),
+            cyborg_dna: cyborg_dna.clone(),
+        };
+        imbibers().save(deps.storage, &info.sender, &cyborg)?;
+
+        // The JumpRing mints the new cyborg's passport; minting fails
+        // (and with it, the swig) if the imbiber already carries one
+        let mint_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: state.portal_contract.to_string(),
+            msg: to_binary(&PortalExecuteMsg::MintPassport {
+                msg: MintMsg {
+                    name: name.clone(),
+                    description,
+                    image,
+                    dna: cyborg_dna.to_string(),
+                    species: species.name.clone(),
+                    sapience_level: species.sapience_level.clone(),
+                    identity: info.sender.clone(),
+                },
+            })?,
+            funds: vec![],
+        });
+        res = res.add_message(mint_msg);
+    }
+
+    if let Some(section31_contract) = state.section31_contract {
+        let msg = CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: section31_contract.to_string(),
+            msg: to_binary(&Section31Execute::Snitch {
+                address: info.sender,
+                name,
+                species,
+            })?,
+            funds: vec![],
+        });
+        res = res.add_submessage(SubMsg::reply_on_error(msg, 1));
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,8 @@
+pub mod check_sapience_level;
+pub mod check_sent_required_payment;
+pub mod cyborg_dna_generator;
+pub mod imbibe_potion;
+pub mod ownership;
+pub mod reindex_imbibers;
+pub mod step_through_jumpring;
+pub mod swigs;
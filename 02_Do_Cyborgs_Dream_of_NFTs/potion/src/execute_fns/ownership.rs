@@ -0,0 +1,84 @@
+use crate::error::ContractError;
+use crate::state::{PendingOwner, State, CONFIG, PENDING_OWNER};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response};
+use cw_utils::Expiration;
+
+pub(crate) fn check_owner(state: &State, info: &MessageInfo) -> Result<(), ContractError> {
+    if state.owner.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn propose_new_owner(
+    new_owner: Addr,
+    expiry: Option<Expiration>,
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    if expiry.map(|e| e.is_expired(&env.block)).unwrap_or(false) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    // A new proposal replaces any previous one
+    let pending = PendingOwner {
+        new_owner: new_owner.clone(),
+        expiry,
+    };
+    PENDING_OWNER.save(deps.storage, &pending)?;
+    Ok(Response::new()
+        .add_attribute("action", "propose_new_owner")
+        .add_attribute("new_owner", new_owner))
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending = PENDING_OWNER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner {})?;
+    if info.sender != pending.new_owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    if pending.expiry.map(|e| e.is_expired(&env.block)).unwrap_or(false) {
+        return Err(ContractError::OwnershipProposalExpired {});
+    }
+
+    let mut state = CONFIG.load(deps.storage)?;
+    state.owner = Some(pending.new_owner);
+    CONFIG.save(deps.storage, &state)?;
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new()
+        .add_attribute("action", "accept_ownership")
+        .add_attribute("owner", info.sender))
+}
+
+pub fn cancel_ownership_proposal(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new().add_attribute("action", "cancel_ownership_proposal"))
+}
+
+pub fn renounce_ownership(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    state.owner = None;
+    CONFIG.save(deps.storage, &state)?;
+    PENDING_OWNER.remove(deps.storage);
+    Ok(Response::new().add_attribute("action", "renounce_ownership"))
+}
@@ -0,0 +1,38 @@
+use crate::error::ContractError;
+use crate::execute_fns::ownership::check_owner;
+use crate::query_fns::{DEFAULT_LIMIT, MAX_LIMIT};
+use crate::state::{imbibers, CONFIG};
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Order, Response, StdResult};
+use cw_storage_plus::Bound;
+
+// Imbibers saved before they were indexed by species are saved again to index
+// them. There can be too many to do in one migration, so the owner works through
+// them a page at a time. Saving imbibers that are already indexed is harmless
+pub fn reindex_imbibers(
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let page = imbibers()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (address, imbiber) in &page {
+        imbibers().save(deps.storage, address, imbiber)?;
+    }
+
+    let mut res = Response::new()
+        .add_attribute("action", "reindex_imbibers")
+        .add_attribute("reindexed", page.len().to_string())
+        .add_attribute("done", (page.len() < limit).to_string());
+    if let Some((last_address, _)) = page.last() {
+        res = res.add_attribute("last_address", last_address);
+    }
+    Ok(res)
+}
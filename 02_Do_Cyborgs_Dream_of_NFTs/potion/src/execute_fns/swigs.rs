@@ -0,0 +1,67 @@
+use crate::error::ContractError;
+use crate::execute_fns::ownership::check_owner;
+use crate::state::CONFIG;
+use cosmwasm_std::{
+    Addr, BankMsg, Coin, DepsMut, MessageInfo, OverflowError, OverflowOperation, Response,
+    StdError,
+};
+
+pub fn refill(amount: u64, deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let mut state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    state.swigs = state.swigs.checked_add(amount).ok_or_else(|| {
+        StdError::overflow(OverflowError::new(OverflowOperation::Add, state.swigs, amount))
+    })?;
+    CONFIG.save(deps.storage, &state)?;
+    Ok(Response::new()
+        .add_attribute("action", "refill")
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("swigs", state.swigs.to_string()))
+}
+
+pub fn set_swig_price(
+    price: Option<Coin>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    state.swig_price = price;
+    CONFIG.save(deps.storage, &state)?;
+    Ok(Response::new().add_attribute("action", "set_swig_price"))
+}
+
+// Lowering the limit doesn't take back swigs already imbibed,
+// it just stops addresses over the new limit imbibing any more
+pub fn set_swig_limit(
+    limit: Option<u64>,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    state.swig_limit = limit;
+    CONFIG.save(deps.storage, &state)?;
+    Ok(Response::new().add_attribute("action", "set_swig_limit"))
+}
+
+// Imbibers pay the potion for their swigs, so
+// the owner needs a way to collect the takings
+pub fn withdraw_funds(
+    to: Addr,
+    amount: Coin,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let state = CONFIG.load(deps.storage)?;
+    check_owner(&state, &info)?;
+    let msg = BankMsg::Send {
+        to_address: to.to_string(),
+        amount: vec![amount.clone()],
+    };
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "withdraw_funds")
+        .add_attribute("to", to)
+        .add_attribute("amount", amount.to_string()))
+}
@@ -0,0 +1,750 @@
+#![cfg(test)]
+use serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{
+    Addr, Coin, Empty, QueryRequest, Response, to_binary, StdError, Uint128, WasmQuery,
+};
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Map;
+use cw_utils::Expiration;
+use cw2::{get_contract_version, set_contract_version};
+
+use crate::contract::{execute, instantiate, migrate};
+use crate::error::ContractError;
+use crate::execute_fns::cyborg_dna_generator::{cyborg_dna_generator, MAX_DNA_LENGTH};
+use crate::execute_fns::step_through_jumpring::DENOM;
+use crate::msg::{
+    AddressSwigsResponse, ExecuteMsg, ImbiberResponse, ImbibersResponse, InstantiateMsg, 
+    MigrateMsg, OwnershipResponse, QueryMsg, SwigResponse,
+};
+use crate::query_fns::imbibers_by_species;
+use crate::state::Imbiber;
+use universe::dna::CyborgDna;
+use universe::migrate::MigrateError;
+use universe::species::{SapienceScale, Species};
+
+use cw721::{NftInfoResponse, NumTokensResponse};
+use passport_token::{
+    ExecuteMsg as Cw721ExecuteMsg, Extension, InstantiateMsg as Cw721InstantiateMsg, 
+    QueryMsg as Cw721QueryMsg,
+};
+use portal::msg::{
+    ExecuteMsg as PortalExecuteMsg, InstantiateMsg as PortalInstantiateMsg, 
+    CollectedFeesResponse, QueryMsg as PortalQueryMsg, TravelerLocationResponse,
+};
+
+static DNA_LENGTH: usize = 16;
+static DNA_MODULUS: u8 = 10;
+
+fn mock_app() -> App {
+    App::default()
+}
+fn mint_native(app: &mut App, beneficiary: String, denom: String, amount: Uint128) {
+    app.sudo(cw_multi_test::SudoMsg::Bank(
+        cw_multi_test::BankSudo::Mint {
+            to_address: beneficiary,
+            amount: vec![Coin { denom, amount }],
+        },
+    ))
+    .unwrap();
+}
+
+pub fn query<M, T>(router: &mut App, target_contract: Addr, msg: M) -> Result<T, StdError>
+where
+    M: Serialize + DeserializeOwned,
+    T: Serialize + DeserializeOwned,
+{
+    router.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: target_contract.to_string(),
+        msg: to_binary(&msg).unwrap(),
+    }))
+}
+
+pub fn contract_potion() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    )
+    .with_reply(crate::contract::reply);
+    Box::new(contract)
+}
+
+pub fn contract_portal() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        portal::contract::execute,
+        portal::contract::instantiate,
+        portal::contract::query,
+    );
+    Box::new(contract)
+}
+
+pub fn contract_cw721() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        passport_token::entry::execute,
+        passport_token::entry::instantiate,
+        passport_token::entry::query,
+    );
+    Box::new(contract)
+}
+
+struct Contracts {
+    potion: Addr,
+    portal: Addr,
+    destination: Addr,
+    passport: Addr,
+}
+
+/// Sets up the full cyberdization flow: a potion minting passports through 
+/// the "earth" portal, and a "jupiter" portal travelers can be sent to
+fn setup_contracts(router: &mut App, owner: &Addr) -> Contracts {
+    let portal_id = router.store_code(contract_portal());
+    let mut portals = vec![];
+    for planet_name in ["earth", "jupiter"] {
+        let msg = PortalInstantiateMsg {
+            planet_name: planet_name.to_string(),
+            planet_sapients: vec![],
+            minimum_sapience: SapienceScale::High,
+            passport_contract: Addr::unchecked("passport"),   // Updated once the passport contract exists
+            potion_contract: Addr::unchecked("potion"),       // Updated once the potion contract exists
+            registry_contract: None,
+            jump_ring_fee: Some(Coin {
+                denom: DENOM.to_string(),
+                amount: Uint128::from(1000000u128),
+            }),
+        };
+        let portal = router
+            .instantiate_contract(portal_id, owner.clone(), &msg, &[], "portal-v1.0", None)
+            .unwrap();
+        portals.push(portal);
+    }
+    let (portal, destination) = (portals[0].clone(), portals[1].clone());
+
+    let cw721_id = router.store_code(contract_cw721());
+    let msg = Cw721InstantiateMsg {
+        name: "Passport Token".to_string(),
+        symbol: "PASS".to_string(),
+        minter: portal.to_string(),
+        unique_owners: true,
+    };
+    let passport = router
+        .instantiate_contract(cw721_id, owner.clone(), &msg, &[], "passport-v1.0", None)
+        .unwrap();
+
+    // The destination stamps arriving travelers' passports, so it must be a minter too
+    let msg = Cw721ExecuteMsg::AddMinter {
+        minter: destination.to_string(),
+        quota: None,
+    };
+    router.execute_contract(owner.clone(), passport.clone(), &msg, &[]).unwrap();
+
+    let potion_id = router.store_code(contract_potion());
+    let msg = InstantiateMsg {
+        owner: owner.clone(),
+        dna_length: DNA_LENGTH,
+        dna_modulus: DNA_MODULUS,
+        portal_contract: portal.clone(),
+        section31_contract: None,
+        swigs: None,
+        swig_price: None,
+        swig_limit: None,
+    };
+    let potion = router
+        .instantiate_contract(potion_id, Addr::unchecked("deployer"), &msg, &[], "potion-v1.0", None)
+        .unwrap();
+
+    // Wire both portals to the potion and passport contracts, and to each other
+    for (this, other) in [(&portal, &destination), (&destination, &portal)] {
+        let update_msgs = vec![
+            PortalExecuteMsg::SetPassportContract { contract: passport.clone() },
+            PortalExecuteMsg::SetPotionContract { contract: potion.clone() },
+            PortalExecuteMsg::SetKnownPortals { to: vec![other.clone()] },
+        ];
+        for msg in update_msgs {
+            router.execute_contract(owner.clone(), this.clone(), &msg, &[]).unwrap();
+        }
+    }
+
+    Contracts {
+        potion,
+        portal,
+        destination,
+        passport,
+    }
+}
+
+fn imbibe_msg(species: &str, sapience_level: SapienceScale) -> ExecuteMsg {
+    ExecuteMsg::ImbibePotion {
+        name: "Traveler Name".to_string(),
+        species: Species {
+            name: species.to_string(),
+            sapience_level,
+        },
+        description: "Ever since you became a cyborg, you've been feeling pretty weird...".to_string(),
+        image: "ipfs://QmZdPdZzZum2jQ7jg1ekfeE3LSz1avAaa42G6mfimw9TEn".to_string(),
+    }
+}
+
+#[test]
+fn imbibing_mints_a_passport() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    let contracts = setup_contracts(&mut app, &owner);
+
+    app.execute_contract(
+        user.clone(), 
+        contracts.potion.clone(), 
+        &imbibe_msg("Cyborg", SapienceScale::High), 
+        &[]
+    )
+    .unwrap();
+
+    // The passport carries the imbiber's freshly generated DNA
+    let nft_query: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: user.to_string(),
+    };
+    let nft_info: NftInfoResponse<Extension> = query(&mut app, contracts.passport.clone(), nft_query).unwrap();
+    let passport = nft_info.extension.unwrap();
+    let expected_dna = CyborgDna::new(cyborg_dna_generator(user.as_str(), DNA_LENGTH, DNA_MODULUS));
+    assert_eq!(passport.dna, Some(expected_dna.to_string()));
+    assert_eq!(passport.identity, Some(user.clone()));
+    assert_eq!(passport.issuer, Some(contracts.portal.clone()));
+    assert_eq!(passport.species, Some("Cyborg".to_string()));
+
+    let swigs: SwigResponse = query(&mut app, contracts.potion.clone(), QueryMsg::NumberOfSwigs {}).unwrap();
+    assert_eq!(swigs.swigs, 2);
+
+    // Imbibing again spends a swig, but the imbiber keeps their first passport
+    app.execute_contract(
+        user.clone(), 
+        contracts.potion.clone(), 
+        &imbibe_msg("Human", SapienceScale::Medium), 
+        &[]
+    )
+    .unwrap();
+    let swigs: SwigResponse = query(&mut app, contracts.potion, QueryMsg::NumberOfSwigs {}).unwrap();
+    assert_eq!(swigs.swigs, 1);
+    let nft_query: Cw721QueryMsg<Extension> = Cw721QueryMsg::NftInfo {
+        token_id: user.to_string(),
+    };
+    let nft_info: NftInfoResponse<Extension> = query(&mut app, contracts.passport, nft_query).unwrap();
+    assert_eq!(nft_info.extension.unwrap().species, Some("Cyborg".to_string()));
+
+    // Passports can't be minted around the potion
+    let mint_res = app.execute_contract(
+        owner, 
+        contracts.portal, 
+        &PortalExecuteMsg::MintPassport {
+            msg: portal::msg::MintMsg {
+                name: "Traveler Name".to_string(),
+                description: "Forged".to_string(),
+                image: "Forged".to_string(),
+                dna: "Forged".to_string(),
+                species: "Cyborg".to_string(),
+                sapience_level: SapienceScale::High,
+                identity: Addr::unchecked("forger"),
+            },
+        }, 
+        &[]
+    );
+    assert!(mint_res.is_err());
+}
+
+#[test]
+fn stepping_through_jump_ring() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let cyborg = Addr::unchecked("cyborg");
+    let human = Addr::unchecked("human");
+    let stranger = Addr::unchecked("stranger");
+    let contracts = setup_contracts(&mut app, &owner);
+
+    for traveler in [&cyborg, &human, &stranger] {
+        mint_native(
+            &mut app,
+            traveler.to_string(),
+            String::from(DENOM),
+            Uint128::from(10000000u128),
+        );
+    }
+    let fee = vec![Coin {
+        denom: DENOM.to_string(),
+        amount: Uint128::from(1000000u128),
+    }];
+
+    app.execute_contract(
+        cyborg.clone(), 
+        contracts.potion.clone(), 
+        &imbibe_msg("Cyborg", SapienceScale::High), 
+        &[]
+    )
+    .unwrap();
+    app.execute_contract(
+        human.clone(), 
+        contracts.potion.clone(), 
+        &imbibe_msg("Human", SapienceScale::Medium), 
+        &[]
+    )
+    .unwrap();
+
+    let step_msg = ExecuteMsg::StepThroughJumpRing {
+        portal: contracts.portal.clone(),
+        destination: contracts.destination.clone(),
+    };
+
+    // Those who haven't imbibed aren't cyborgs
+    let err = app
+        .execute_contract(stranger, contracts.potion.clone(), &step_msg, &fee)
+        .unwrap_err();
+    assert_eq!(ContractError::NotACyborg {}, err.downcast().unwrap());
+
+    // Humans aren't sapient enough for a High sapience portal
+    let err = app
+        .execute_contract(human, contracts.potion.clone(), &step_msg, &fee)
+        .unwrap_err();
+    assert_eq!(ContractError::NotSapientEnough {}, err.downcast().unwrap());
+
+    // Cyborgs must pay for passage
+    let unpaid_res = app.execute_contract(cyborg.clone(), contracts.potion.clone(), &step_msg, &[]);
+    assert!(unpaid_res.is_err());
+
+    app.execute_contract(cyborg.clone(), contracts.potion.clone(), &step_msg, &fee)
+        .unwrap();
+    let location: TravelerLocationResponse = query(
+        &mut app,
+        contracts.destination.clone(),
+        PortalQueryMsg::TravelerLocation { traveler: cyborg },
+    )
+    .unwrap();
+    assert_eq!(location.location, Some(contracts.destination));
+
+    // The fee ends up with the portal, not the potion
+    let collected: CollectedFeesResponse = query(
+        &mut app, 
+        contracts.portal.clone(), 
+        PortalQueryMsg::CollectedFees {},
+    )
+    .unwrap();
+    assert_eq!(collected.fees, fee);
+    let potion_balance = app.wrap().query_balance(contracts.potion, DENOM).unwrap();
+    assert_eq!(potion_balance.amount, Uint128::zero());
+}
+
+#[test]
+fn selling_swigs() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    let other_user = Addr::unchecked("other_user");
+    let contracts = setup_contracts(&mut app, &owner);
+    let potion = contracts.potion;
+    for imbiber in [&user, &other_user] {
+        mint_native(&mut app, imbiber.to_string(), String::from(DENOM), Uint128::from(1000u128));
+    }
+    let price = Coin {
+        denom: DENOM.to_string(),
+        amount: Uint128::from(100u128),
+    };
+    let payment = vec![price.clone()];
+
+    // Only the owner can price, limit or refill swigs
+    let price_msg = ExecuteMsg::SetSwigPrice { price: Some(price.clone()) };
+    let limit_msg = ExecuteMsg::SetSwigLimit { limit: Some(1) };
+    let refill_msg = ExecuteMsg::Refill { amount: 5 };
+    for msg in [&price_msg, &limit_msg, &refill_msg] {
+        let err = app
+            .execute_contract(user.clone(), potion.clone(), msg, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+        app.execute_contract(owner.clone(), potion.clone(), msg, &[]).unwrap();
+    }
+    let swigs: SwigResponse = query(&mut app, potion.clone(), QueryMsg::NumberOfSwigs {}).unwrap();
+    assert_eq!(
+        swigs,
+        SwigResponse {
+            swigs: 8,
+            price: Some(price.clone()),
+            limit: Some(1),
+        }
+    );
+
+    // Swigs must be paid for
+    let err = app
+        .execute_contract(user.clone(), potion.clone(), &imbibe_msg("Cyborg", SapienceScale::High), &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InsufficientPayment { required: price.to_string() },
+        err.downcast().unwrap()
+    );
+    app.execute_contract(
+        user.clone(), 
+        potion.clone(), 
+        &imbibe_msg("Cyborg", SapienceScale::High), 
+        &payment
+    )
+    .unwrap();
+    let user_swigs: AddressSwigsResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::SwigsFor { address: user.clone() },
+    )
+    .unwrap();
+    assert_eq!(user_swigs.imbibed, 1);
+    assert_eq!(user_swigs.remaining, 0);
+
+    // Each address can only have its limit, however much is left in the flask
+    let err = app
+        .execute_contract(
+            user.clone(), 
+            potion.clone(), 
+            &imbibe_msg("Cyborg", SapienceScale::High), 
+            &payment
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::SwigLimitReached { limit: 1 }, err.downcast().unwrap());
+    let other_swigs: AddressSwigsResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::SwigsFor { address: other_user.clone() },
+    )
+    .unwrap();
+    assert_eq!(other_swigs.remaining, 1);
+    app.execute_contract(
+        other_user, 
+        potion.clone(), 
+        &imbibe_msg("Cyborg", SapienceScale::High), 
+        &payment
+    )
+    .unwrap();
+    let swigs: SwigResponse = query(&mut app, potion.clone(), QueryMsg::NumberOfSwigs {}).unwrap();
+    assert_eq!(swigs.swigs, 6);
+
+    // The owner collects the takings
+    let withdraw_msg = ExecuteMsg::WithdrawFunds {
+        to: owner.clone(),
+        amount: Coin {
+            denom: DENOM.to_string(),
+            amount: Uint128::from(200u128),
+        },
+    };
+    let err = app
+        .execute_contract(user, potion.clone(), &withdraw_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(owner.clone(), potion.clone(), &withdraw_msg, &[]).unwrap();
+    let owner_balance = app.wrap().query_balance(owner, DENOM).unwrap();
+    assert_eq!(owner_balance.amount, Uint128::from(200u128));
+    let potion_balance = app.wrap().query_balance(potion, DENOM).unwrap();
+    assert_eq!(potion_balance.amount, Uint128::zero());
+}
+
+#[test]
+fn imbibing_up_to_the_swig_limit() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let user = Addr::unchecked("user");
+    let contracts = setup_contracts(&mut app, &owner);
+    let potion = contracts.potion;
+
+    let limit_msg = ExecuteMsg::SetSwigLimit { limit: Some(2) };
+    app.execute_contract(owner, potion.clone(), &limit_msg, &[]).unwrap();
+
+    // Both swigs go down, though only the first mints a passport
+    for _ in 0..2 {
+        app.execute_contract(user.clone(), potion.clone(), &imbibe_msg("Cyborg", SapienceScale::High), &[])
+            .unwrap();
+    }
+    let user_swigs: AddressSwigsResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::SwigsFor { address: user.clone() },
+    )
+    .unwrap();
+    assert_eq!(user_swigs.imbibed, 2);
+    assert_eq!(user_swigs.remaining, 0);
+    let num_tokens: NumTokensResponse = query(
+        &mut app,
+        contracts.passport,
+        Cw721QueryMsg::<Extension>::NumTokens {},
+    )
+    .unwrap();
+    assert_eq!(num_tokens.count, 1);
+
+    let err = app
+        .execute_contract(user, potion, &imbibe_msg("Cyborg", SapienceScale::High), &[])
+        .unwrap_err();
+    assert_eq!(ContractError::SwigLimitReached { limit: 2 }, err.downcast().unwrap());
+}
+
+#[test]
+fn querying_imbibers() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let contracts = setup_contracts(&mut app, &owner);
+    let potion = contracts.potion;
+    let cyborgs = [Addr::unchecked("cyborg_a"), Addr::unchecked("cyborg_b")];
+    let human = Addr::unchecked("human");
+
+    for cyborg in &cyborgs {
+        app.execute_contract(cyborg.clone(), potion.clone(), &imbibe_msg("Cyborg", SapienceScale::High), &[])
+            .unwrap();
+    }
+    app.execute_contract(human.clone(), potion.clone(), &imbibe_msg("Human", SapienceScale::Medium), &[])
+        .unwrap();
+
+    // Imbibers carry the DNA the potion generated for them
+    let res: ImbiberResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::Imbiber { address: human.clone() },
+    )
+    .unwrap();
+    let imbiber = res.imbiber.unwrap();
+    let expected_dna = CyborgDna::new(cyborg_dna_generator(human.as_str(), DNA_LENGTH, DNA_MODULUS));
+    assert_eq!(imbiber.cyborg_dna, expected_dna);
+    assert_eq!(imbiber.species.name, "Human".to_string());
+    let res: ImbiberResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::Imbiber { address: Addr::unchecked("stranger") },
+    )
+    .unwrap();
+    assert_eq!(res.imbiber, None);
+
+    // Imbibers are paged through in address order
+    let page: ImbibersResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::Imbibers { start_after: None, limit: Some(2) },
+    )
+    .unwrap();
+    let addresses: Vec<Addr> = page.imbibers.iter().map(|i| i.address.clone()).collect();
+    assert_eq!(addresses, cyborgs.to_vec());
+    let page: ImbibersResponse = query(
+        &mut app,
+        potion.clone(),
+        QueryMsg::Imbibers { start_after: Some(cyborgs[1].clone()), limit: Some(2) },
+    )
+    .unwrap();
+    assert_eq!(page.imbibers.len(), 1);
+    assert_eq!(page.imbibers[0].address, human);
+
+    let by_species = |app: &mut App, species: &str, start_after: Option<Addr>| -> Vec<Addr> {
+        let msg = QueryMsg::ImbibersBySpecies {
+            species: species.to_string(),
+            start_after,
+            limit: None,
+        };
+        let res: ImbibersResponse = query(app, potion.clone(), msg).unwrap();
+        res.imbibers.into_iter().map(|i| i.address).collect()
+    };
+    assert_eq!(by_species(&mut app, "Cyborg", None), cyborgs.to_vec());
+    assert_eq!(by_species(&mut app, "Cyborg", Some(cyborgs[0].clone())), vec![cyborgs[1].clone()]);
+    assert_eq!(by_species(&mut app, "Human", None), vec![human]);
+    assert!(by_species(&mut app, "Vulcan", None).is_empty());
+}
+
+#[test]
+fn transferring_ownership() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let new_owner = Addr::unchecked("new_owner");
+    let stranger = Addr::unchecked("stranger");
+    let contracts = setup_contracts(&mut app, &owner);
+    let potion = contracts.potion;
+
+    // The owner named at instantiation, rather than the deployer, owns the potion
+    let ownership: OwnershipResponse = query(&mut app, potion.clone(), QueryMsg::Ownership {}).unwrap();
+    assert_eq!(ownership.owner, Some(owner.clone()));
+
+    let accept_msg = ExecuteMsg::AcceptOwnership {};
+    let cancel_msg = ExecuteMsg::CancelOwnershipProposal {};
+    let renounce_msg = ExecuteMsg::RenounceOwnership {};
+    let err = app
+        .execute_contract(new_owner.clone(), potion.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err.downcast().unwrap());
+
+    // Only the owner can propose, cancel or renounce
+    let current_height = app.block_info().height;
+    let propose_msg = ExecuteMsg::ProposeNewOwner {
+        new_owner: new_owner.clone(),
+        expiry: Some(Expiration::AtHeight(current_height + 5)),
+    };
+    for msg in [&propose_msg, &cancel_msg, &renounce_msg] {
+        let err = app
+            .execute_contract(stranger.clone(), potion.clone(), msg, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    }
+    app.execute_contract(owner.clone(), potion.clone(), &propose_msg, &[]).unwrap();
+
+    // Only the proposed owner can accept, and only before the proposal expires
+    let err = app
+        .execute_contract(stranger, potion.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.update_block(|block| block.height += 5);
+    let err = app
+        .execute_contract(new_owner.clone(), potion.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::OwnershipProposalExpired {}, err.downcast().unwrap());
+
+    // Cancelled proposals can't be accepted either
+    let propose_msg = ExecuteMsg::ProposeNewOwner {
+        new_owner: new_owner.clone(),
+        expiry: None,
+    };
+    app.execute_contract(owner.clone(), potion.clone(), &propose_msg, &[]).unwrap();
+    app.execute_contract(owner.clone(), potion.clone(), &cancel_msg, &[]).unwrap();
+    let err = app
+        .execute_contract(new_owner.clone(), potion.clone(), &accept_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::NoPendingOwner {}, err.downcast().unwrap());
+
+    app.execute_contract(owner.clone(), potion.clone(), &propose_msg, &[]).unwrap();
+    app.execute_contract(new_owner.clone(), potion.clone(), &accept_msg, &[]).unwrap();
+    let ownership: OwnershipResponse = query(&mut app, potion.clone(), QueryMsg::Ownership {}).unwrap();
+    assert_eq!(
+        ownership,
+        OwnershipResponse {
+            owner: Some(new_owner.clone()),
+            pending_owner: None,
+            pending_expiry: None,
+        }
+    );
+
+    // The previous owner can't renounce on the new owner's behalf
+    let err = app
+        .execute_contract(owner, potion.clone(), &renounce_msg, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    app.execute_contract(new_owner, potion.clone(), &renounce_msg, &[]).unwrap();
+    let ownership: OwnershipResponse = query(&mut app, potion, QueryMsg::Ownership {}).unwrap();
+    assert_eq!(ownership.owner, None);
+}
+
+#[test]
+fn instantiating_with_invalid_dna() {
+    let mut deps = mock_dependencies();
+    let too_long = MAX_DNA_LENGTH + 1;
+    let cases = [
+        (0, DNA_MODULUS, ContractError::InvalidDnaLength { length: 0 }),
+        (too_long, DNA_MODULUS, ContractError::InvalidDnaLength { length: too_long }),
+        (DNA_LENGTH, 0, ContractError::InvalidDnaModulus {}),
+    ];
+    for (dna_length, dna_modulus, expected) in cases {
+        let msg = InstantiateMsg {
+            owner: Addr::unchecked("owner"),
+            dna_length,
+            dna_modulus,
+            portal_contract: Addr::unchecked("portal"),
+            section31_contract: None,
+            swigs: None,
+            swig_price: None,
+            swig_limit: None,
+        };
+        let err = instantiate(deps.as_mut(), mock_env(), mock_info("deployer", &[]), msg).unwrap_err();
+        assert_eq!(err, expected);
+    }
+
+    // The longest DNA a hash can provide is fine
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("owner"),
+        dna_length: MAX_DNA_LENGTH,
+        dna_modulus: 1,
+        portal_contract: Addr::unchecked("portal"),
+        section31_contract: None,
+        swigs: None,
+        swig_price: None,
+        swig_limit: None,
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("deployer", &[]), msg).unwrap();
+}
+
+#[test]
+fn migrating_potion() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("owner"),
+        dna_length: DNA_LENGTH,
+        dna_modulus: DNA_MODULUS,
+        portal_contract: Addr::unchecked("portal"),
+        section31_contract: None,
+        swigs: None,
+        swig_price: None,
+        swig_limit: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("deployer", &[]), msg).unwrap();
+
+    // Older versions stored imbibers without indexing them by species
+    let legacy_imbibers: Map<&Addr, Imbiber> = Map::new("imbiber");
+    let mut saved = vec![];
+    for address in ["alice", "bob", "carol"] {
+        let address = Addr::unchecked(address);
+        let imbiber = Imbiber {
+            address: address.clone(),
+            species: Species {
+                name: "Cyborg".to_string(),
+                sapience_level: SapienceScale::High,
+            },
+            name: "Traveler Name".to_string(),
+            cyborg_dna: CyborgDna::new(cyborg_dna_generator(address.as_str(), DNA_LENGTH, DNA_MODULUS)),
+        };
+        legacy_imbibers.save(&mut deps.storage, &address, &imbiber).unwrap();
+        saved.push(imbiber);
+    }
+
+    // Upgrading from an older version works
+    set_contract_version(&mut deps.storage, "crates.io:potion", "0.0.1").unwrap();
+    migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.contract, "crates.io:potion".to_string());
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION").to_string());
+    let res = imbibers_by_species(deps.as_ref(), "Cyborg".to_string(), None, None).unwrap();
+    assert!(res.imbibers.is_empty());
+
+    // Then the owner indexes those imbibers, a page at a time
+    let reindex_msg = |start_after: Option<String>| ExecuteMsg::ReindexImbibers {
+        start_after: start_after.map(Addr::unchecked),
+        limit: Some(2),
+    };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("cyborg", &[]), reindex_msg(None)).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let attribute = |res: &Response, key: &str| {
+        res.attributes.iter().find(|a| a.key == key).map(|a| a.value.clone())
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), reindex_msg(None)).unwrap();
+    assert_eq!(attribute(&res, "done"), Some("false".to_string()));
+    let indexed = imbibers_by_species(deps.as_ref(), "Cyborg".to_string(), None, None).unwrap();
+    assert_eq!(indexed.imbibers, saved[..2].to_vec());
+    let start_after = attribute(&res, "last_address");
+    let res = execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), reindex_msg(start_after)).unwrap();
+    assert_eq!(attribute(&res, "done"), Some("true".to_string()));
+    let indexed = imbibers_by_species(deps.as_ref(), "Cyborg".to_string(), None, None).unwrap();
+    assert_eq!(indexed.imbibers, saved);
+
+    // Downgrading doesn't
+    set_contract_version(&mut deps.storage, "crates.io:potion", "99.0.0").unwrap();
+    let err = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Migrate(MigrateError::CannotDowngrade {
+            stored: "99.0.0".to_string(),
+            current: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    );
+
+    // Neither does migrating some other contract
+    set_contract_version(&mut deps.storage, "crates.io:portal", "0.0.1").unwrap();
+    let err = migrate(deps.as_mut(), env, MigrateMsg {}).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Migrate(MigrateError::WrongContract {
+            expected: "crates.io:potion".to_string(),
+            found: "crates.io:portal".to_string(),
+        })
+    );
+}
@@ -0,0 +1,122 @@
+use cosmwasm_std::{Addr, Coin};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use universe::species::Species;
+
+use crate::state::Imbiber;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub owner: Addr,
+    pub dna_length: usize,
+    pub dna_modulus: u8,
+    pub portal_contract: Addr,
+    pub section31_contract: Option<Addr>,
+    pub swigs: Option<u64>,             // Initial supply, 3 swigs if unset
+    pub swig_price: Option<Coin>,
+    pub swig_limit: Option<u64>,        // Per address
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ExecuteMsg {
+    ImbibePotion {
+        name: String,
+        species: Species,
+        description: String,
+        image: String,
+    },
+    StepThroughJumpRing {
+        portal: Addr,
+        destination: Addr,
+    },
+    ProposeNewOwner {
+        new_owner: Addr,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
+    RenounceOwnership {},
+    Refill {
+        amount: u64,
+    },
+    SetSwigPrice {
+        price: Option<Coin>,
+    },
+    SetSwigLimit {
+        limit: Option<u64>,
+    },
+    WithdrawFunds {
+        to: Addr,
+        amount: Coin,
+    },
+    // Indexes imbibers saved before the species index existed, a page at a time.
+    // Pass the returned `last_address` as `start_after` until it reports `done`
+    ReindexImbibers {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum QueryMsg {
+    NumberOfSwigs {},
+    SwigsFor { address: Addr },
+    Imbiber {
+        address: Addr,
+    },
+    Imbibers {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    ImbibersBySpecies {
+        species: String,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    Ownership {},
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwigResponse {
+    pub swigs: u64,
+    pub price: Option<Coin>,
+    pub limit: Option<u64>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AddressSwigsResponse {
+    pub address: Addr,
+    pub imbibed: u64,
+    pub remaining: u64,     // Swigs the address can still imbibe, given its limit and the supply
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ImbiberResponse {
+    pub imbiber: Option<Imbiber>,   // None if the address hasn't imbibed
+}
+
+/// Imbibers in address order. Pass the last address as `start_after` to fetch the next page
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ImbibersResponse {
+    pub imbibers: Vec<Imbiber>,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OwnershipResponse {
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
@@ -0,0 +1,85 @@
+use crate::msg::{
+    AddressSwigsResponse, ImbiberResponse, ImbibersResponse, OwnershipResponse, SwigResponse,
+};
+use crate::state::{imbibers as imbibers_map, CONFIG, PENDING_OWNER, SWIGS_IMBIBED};
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+pub(crate) const DEFAULT_LIMIT: u32 = 10;
+pub(crate) const MAX_LIMIT: u32 = 100;
+
+pub fn number_of_swigs(deps: Deps) -> StdResult<SwigResponse> {
+    let state = CONFIG.load(deps.storage)?;
+    Ok(SwigResponse {
+        swigs: state.swigs,
+        price: state.swig_price,
+        limit: state.swig_limit,
+    })
+}
+
+pub fn swigs_for(deps: Deps, address: Addr) -> StdResult<AddressSwigsResponse> {
+    let state = CONFIG.load(deps.storage)?;
+    let imbibed = SWIGS_IMBIBED
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let remaining = match state.swig_limit {
+        Some(limit) => limit.saturating_sub(imbibed).min(state.swigs),
+        None => state.swigs,
+    };
+    Ok(AddressSwigsResponse {
+        address,
+        imbibed,
+        remaining,
+    })
+}
+
+pub fn imbiber(deps: Deps, address: Addr) -> StdResult<ImbiberResponse> {
+    let imbiber = imbibers_map().may_load(deps.storage, &address)?;
+    Ok(ImbiberResponse { imbiber })
+}
+
+pub fn imbibers(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<ImbibersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let imbibers = imbibers_map()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, imbiber)| imbiber))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ImbibersResponse { imbibers })
+}
+
+pub fn imbibers_by_species(
+    deps: Deps,
+    species: String,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<ImbibersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let imbibers = imbibers_map()
+        .idx
+        .species
+        .prefix(species)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, imbiber)| imbiber))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(ImbibersResponse { imbibers })
+}
+
+pub fn ownership(deps: Deps) -> StdResult<OwnershipResponse> {
+    let state = CONFIG.load(deps.storage)?;
+    let pending = PENDING_OWNER.may_load(deps.storage)?;
+    Ok(OwnershipResponse {
+        owner: state.owner,
+        pending_owner: pending.as_ref().map(|p| p.new_owner.clone()),
+        pending_expiry: pending.and_then(|p| p.expiry),
+    })
+}
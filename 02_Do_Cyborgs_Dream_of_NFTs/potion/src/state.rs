@@ -0,0 +1,66 @@
+use cosmwasm_std::{Addr, Coin};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use universe::dna::CyborgDna;
+use universe::species::Species;
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct State {
+    pub owner: Option<Addr>,                   // None once ownership has been renounced
+    pub dna_length: usize,
+    pub dna_modulus: u8,
+    pub swigs: u64,                             // Swigs left in the flask, for everyone
+    #[serde(default)]
+    pub swig_price: Option<Coin>,               // Charged for each swig, when set
+    #[serde(default)]
+    pub swig_limit: Option<u64>,                // Most swigs any one address can imbibe, when set
+    pub portal_contract: Addr,                  // JumpRing that mints passports for imbibers
+    pub section31_contract: Option<Addr>,       // Imbibers are reported here, when set
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Imbiber {
+    pub address: Addr,
+    pub species: Species,
+    pub name: String,
+    pub cyborg_dna: CyborgDna,
+}
+
+pub const CONFIG: Item<State> = Item::new("config");
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct PendingOwner {
+    pub new_owner: Addr,
+    pub expiry: Option<Expiration>,
+}
+
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+
+pub struct ImbiberIndexes<'a> {
+    pub species: MultiIndex<'a, String, Imbiber, &'a Addr>,
+}
+
+impl<'a> IndexList<Imbiber> for ImbiberIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Imbiber>> + '_> {
+        let v: Vec<&dyn Index<Imbiber>> = vec![&self.species];
+        Box::new(v.into_iter())
+    }
+}
+
+// Everyone who's imbibed the potion, keyed by address and indexed by species name
+pub fn imbibers<'a>() -> IndexedMap<'a, &'a Addr, Imbiber, ImbiberIndexes<'a>> {
+    let indexes = ImbiberIndexes {
+        species: MultiIndex::new(
+            |_pk, d: &Imbiber| d.species.name.clone(),
+            "imbiber",
+            "imbiber__species",
+        ),
+    };
+    IndexedMap::new("imbiber", indexes)
+}
+
+// Swigs each address has imbibed, counted against the `swig_limit`
+pub const SWIGS_IMBIBED: Map<&Addr, u64> = Map::new("swigs_imbibed");
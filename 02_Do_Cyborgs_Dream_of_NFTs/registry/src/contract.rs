@@ -0,0 +1,53 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use crate::query_fns::{all_portals, portal, portals_by_planet, portals_by_sapience};
+use crate::execute_fns::{deregister_portal, register_portal, set_owner};
+use crate::state::{Config, CONFIG};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Portal { portal: address } => portal(address, deps),
+        QueryMsg::Portals { start_after, limit } => all_portals(start_after, limit, deps),
+        QueryMsg::PortalsByPlanet { planet_name, start_after, limit } => {
+            portals_by_planet(planet_name, start_after, limit, deps)
+        }
+        QueryMsg::PortalsBySapience { sapience_level, start_after, limit } => {
+            portals_by_sapience(sapience_level, start_after, limit, deps)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::RegisterPortal { portal, planet_name } => {
+            register_portal(portal, planet_name, deps, info)
+        }
+        ExecuteMsg::DeregisterPortal { portal } => deregister_portal(portal, deps, info),
+        ExecuteMsg::SetOwner { to } => set_owner(to, deps, info),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = Config {
+        owner: info.sender,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("owner", config.owner))
+}
@@ -0,0 +1,77 @@
+use cosmwasm_std::{to_binary, DepsMut, MessageInfo, QueryRequest, Response, WasmQuery};
+
+use crate::error::ContractError;
+use crate::msg::PortalQueryMsg;
+use crate::state::{portals, PortalInfo, CONFIG};
+
+use universe::species::SapienceResponse;
+
+pub fn register_portal(
+    portal: String,
+    planet_name: String,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // Only the registry owner can register portals. JumpRings trust every
+    // registered portal to announce arrivals, so portals can't vouch for themselves
+    let portal = deps.api.addr_validate(&portal)?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // The sapience requirement is read from the portal itself so the two
+    // can't disagree. Re-registering a portal overwrites its previous entry,
+    // which is how a portal's changed requirement reaches the registry
+    let query = WasmQuery::Smart {
+        contract_addr: portal.to_string(),
+        msg: to_binary(&PortalQueryMsg::MinimumSapience {})?,
+    };
+    let res: SapienceResponse = deps.querier.query(&QueryRequest::Wasm(query))?;
+    let portal_info = PortalInfo {
+        portal: portal.clone(),
+        planet_name: planet_name.clone(),
+        minimum_sapience: res.level,
+    };
+    portals().save(deps.storage, &portal, &portal_info)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "register_portal")
+        .add_attribute("portal", portal)
+        .add_attribute("planet_name", planet_name))
+}
+
+pub fn deregister_portal(
+    portal: String,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let portal = deps.api.addr_validate(&portal)?;
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner && info.sender != portal {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !portals().has(deps.storage, &portal) {
+        return Err(ContractError::PortalNotRegistered { portal: portal.into() });
+    }
+    portals().remove(deps.storage, &portal)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deregister_portal")
+        .add_attribute("portal", portal))
+}
+
+pub fn set_owner(
+    to: String,
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+    config.owner = deps.api.addr_validate(&to)?;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(Response::new().add_attribute("action", "set_owner"))
+}
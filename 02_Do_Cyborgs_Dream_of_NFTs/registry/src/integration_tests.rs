@@ -0,0 +1,261 @@
+#![cfg(test)]
+use serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, QueryRequest, Response, to_binary,
+    StdError, StdResult, WasmQuery,
+};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+use crate::error::ContractError;
+use crate::msg::{
+    ExecuteMsg, InstantiateMsg, PortalQueryMsg, PortalResponse, PortalsResponse, QueryMsg,
+};
+use crate::state::PortalInfo;
+use universe::species::{SapienceResponse, SapienceScale};
+
+fn mock_app() -> App {
+    App::default()
+}
+
+pub fn query<M, T>(router: &mut App, target_contract: Addr, msg: M) -> Result<T, StdError>
+where
+    M: Serialize + DeserializeOwned,
+    T: Serialize + DeserializeOwned,
+{
+    router.wrap().query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: target_contract.to_string(),
+        msg: to_binary(&msg).unwrap(),
+    }))
+}
+
+pub fn contract_registry() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        crate::contract::execute,
+        crate::contract::instantiate,
+        crate::contract::query,
+    );
+    Box::new(contract)
+}
+
+// Stands in for a JumpRing, answering only the sapience query the registry makes
+const MOCK_SAPIENCE: Item<SapienceResponse> = Item::new("sapience");
+
+pub fn contract_mock_portal() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |deps: DepsMut, _: Env, _: MessageInfo, msg: SapienceResponse| -> StdResult<Response> {
+            MOCK_SAPIENCE.save(deps.storage, &msg)?;
+            Ok(Response::new())
+        },
+        |deps: Deps, _: Env, msg: PortalQueryMsg| -> StdResult<Binary> {
+            match msg {
+                PortalQueryMsg::MinimumSapience {} => to_binary(&MOCK_SAPIENCE.load(deps.storage)?),
+            }
+        },
+    );
+    Box::new(contract)
+}
+
+fn create_registry(router: &mut App, owner: &Addr) -> Addr {
+    let registry_id = router.store_code(contract_registry());
+    router
+        .instantiate_contract(
+            registry_id,
+            owner.clone(),
+            &InstantiateMsg {},
+            &[],
+            "registry",
+            None,
+        )
+        .unwrap()
+}
+
+fn create_portal(router: &mut App, owner: &Addr, minimum_sapience: SapienceScale) -> Addr {
+    let portal_id = router.store_code(contract_mock_portal());
+    router
+        .instantiate_contract(
+            portal_id,
+            owner.clone(),
+            &SapienceResponse { level: minimum_sapience },
+            &[],
+            "portal",
+            None,
+        )
+        .unwrap()
+}
+
+fn register(router: &mut App, sender: &Addr, registry: &Addr, portal: &Addr, planet_name: &str) {
+    let msg = ExecuteMsg::RegisterPortal {
+        portal: portal.to_string(),
+        planet_name: planet_name.to_string(),
+    };
+    router
+        .execute_contract(sender.clone(), registry.clone(), &msg, &[])
+        .unwrap();
+}
+
+#[test]
+fn registering_portals() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let registry = create_registry(&mut app, &owner);
+    let portal1 = create_portal(&mut app, &owner, SapienceScale::High);
+
+    // Only the registry owner can register a portal, not even the portal itself
+    let msg = ExecuteMsg::RegisterPortal {
+        portal: portal1.to_string(),
+        planet_name: "earth".to_string(),
+    };
+    for sender in [Addr::unchecked("stranger"), portal1.clone()] {
+        let err = app
+            .execute_contract(sender, registry.clone(), &msg, &[])
+            .unwrap_err();
+        assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    }
+
+    // The sapience requirement comes from the portal
+    register(&mut app, &owner, &registry, &portal1, "earth");
+    let res: PortalResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::Portal { portal: portal1.clone() },
+    )
+    .unwrap();
+    assert_eq!(
+        res.portal,
+        PortalInfo {
+            portal: portal1.clone(),
+            planet_name: "earth".to_string(),
+            minimum_sapience: SapienceScale::High,
+        }
+    );
+
+    // Addresses that aren't portals can't be registered
+    let msg = ExecuteMsg::RegisterPortal {
+        portal: "stranger".to_string(),
+        planet_name: "earth".to_string(),
+    };
+    app.execute_contract(owner.clone(), registry.clone(), &msg, &[]).unwrap_err();
+
+    // Re-registering moves the portal to its new planet
+    register(&mut app, &owner, &registry, &portal1, "mars");
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::PortalsByPlanet { planet_name: "earth".to_string(), start_after: None, limit: None },
+    )
+    .unwrap();
+    assert!(res.portals.is_empty());
+
+    // Deregistered portals can no longer be found
+    let msg = ExecuteMsg::DeregisterPortal { portal: portal1.to_string() };
+    app.execute_contract(owner.clone(), registry.clone(), &msg, &[]).unwrap();
+    let res: Result<PortalResponse, StdError> = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::Portal { portal: portal1.clone() },
+    );
+    assert!(res.is_err());
+    let err = app
+        .execute_contract(owner, registry, &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        ContractError::PortalNotRegistered { portal: portal1.to_string() },
+        err.downcast().unwrap()
+    );
+}
+
+#[test]
+fn paginating_portals() {
+    let mut app = mock_app();
+    let owner = Addr::unchecked("owner");
+    let registry = create_registry(&mut app, &owner);
+
+    let portal1 = create_portal(&mut app, &owner, SapienceScale::High);
+    let portal2 = create_portal(&mut app, &owner, SapienceScale::Medium);
+    let portal3 = create_portal(&mut app, &owner, SapienceScale::High);
+    let portal4 = create_portal(&mut app, &owner, SapienceScale::High);
+    register(&mut app, &owner, &registry, &portal1, "earth");
+    register(&mut app, &owner, &registry, &portal2, "earth");
+    register(&mut app, &owner, &registry, &portal3, "earth");
+    register(&mut app, &owner, &registry, &portal4, "mars");
+
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::Portals { start_after: None, limit: Some(3) },
+    )
+    .unwrap();
+    assert_eq!(res.portals.len(), 3);
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::Portals { start_after: Some(portal3.clone()), limit: None },
+    )
+    .unwrap();
+    assert_eq!(res.portals[0].portal, portal4);
+
+    // Portals on earth, one page at a time
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::PortalsByPlanet { planet_name: "earth".to_string(), start_after: None, limit: Some(2) },
+    )
+    .unwrap();
+    let page: Vec<Addr> = res.portals.into_iter().map(|p| p.portal).collect();
+    assert_eq!(page, vec![portal1.clone(), portal2.clone()]);
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::PortalsByPlanet { 
+            planet_name: "earth".to_string(), 
+            start_after: Some(portal2.clone()), 
+            limit: Some(2),
+        },
+    )
+    .unwrap();
+    let page: Vec<Addr> = res.portals.into_iter().map(|p| p.portal).collect();
+    assert_eq!(page, vec![portal3.clone()]);
+
+    // Portals a highly sapient traveler can use, lowest requirement first
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::PortalsBySapience { sapience_level: SapienceScale::High, start_after: None, limit: Some(2) },
+    )
+    .unwrap();
+    let page: Vec<Addr> = res.portals.into_iter().map(|p| p.portal).collect();
+    assert_eq!(page, vec![portal2.clone(), portal1.clone()]);
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::PortalsBySapience {
+            sapience_level: SapienceScale::High,
+            start_after: Some(portal1),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let page: Vec<Addr> = res.portals.into_iter().map(|p| p.portal).collect();
+    assert_eq!(page, vec![portal3, portal4]);
+
+    // Portals requiring high sapience are out of reach of a less sapient one
+    let res: PortalsResponse = query(
+        &mut app,
+        registry.clone(),
+        QueryMsg::PortalsBySapience { sapience_level: SapienceScale::Medium, start_after: None, limit: None },
+    )
+    .unwrap();
+    let page: Vec<Addr> = res.portals.into_iter().map(|p| p.portal).collect();
+    assert_eq!(page, vec![portal2]);
+    let res: PortalsResponse = query(
+        &mut app,
+        registry,
+        QueryMsg::PortalsBySapience { sapience_level: SapienceScale::Low, start_after: None, limit: None },
+    )
+    .unwrap();
+    assert!(res.portals.is_empty());
+}
@@ -0,0 +1,7 @@
+pub mod contract;
+pub mod error;
+pub mod execute_fns;
+pub mod msg;
+pub mod query_fns;
+pub mod state;
+mod integration_tests;
@@ -0,0 +1,47 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::Addr;
+use universe::species::SapienceScale;
+
+use crate::state::PortalInfo;
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum QueryMsg {
+    Portal { portal: Addr },
+    Portals { start_after: Option<Addr>, limit: Option<u32> },
+    PortalsByPlanet { planet_name: String, start_after: Option<Addr>, limit: Option<u32> },
+    // Portals whose minimum sapience is at most `sapience_level`
+    PortalsBySapience { sapience_level: SapienceScale, start_after: Option<Addr>, limit: Option<u32> },
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ExecuteMsg {
+    RegisterPortal { portal: String, planet_name: String },
+    DeregisterPortal { portal: String },
+    SetOwner { to: String },
+}
+
+// The JumpRing query the registry relies on, mirroring the portal's own QueryMsg
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PortalQueryMsg {
+    MinimumSapience {},
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PortalResponse {
+    pub portal: PortalInfo,
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PortalsResponse {
+    pub portals: Vec<PortalInfo>,
+}
@@ -0,0 +1,82 @@
+use cosmwasm_std::{to_binary, Addr, Binary, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+use crate::msg::{PortalResponse, PortalsResponse};
+use crate::state::{portals, PortalInfo};
+use universe::species::SapienceScale;
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 100;
+
+pub fn portal(portal: Addr, deps: Deps) -> StdResult<Binary> {
+    let portal = portals().load(deps.storage, &portal)?;
+    to_binary(&PortalResponse { portal })
+}
+
+pub fn all_portals(
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+    deps: Deps,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let portals: Vec<PortalInfo> = portals()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, portal)| portal))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&PortalsResponse { portals })
+}
+
+pub fn portals_by_planet(
+    planet_name: String,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+    deps: Deps,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let portals: Vec<PortalInfo> = portals()
+        .idx
+        .planet
+        .prefix(planet_name)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, portal)| portal))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&PortalsResponse { portals })
+}
+
+// Portals a traveler of the given sapience can use, i.e. those requiring at
+// most that level, ordered by requirement and then by address
+pub fn portals_by_sapience(
+    sapience_level: SapienceScale,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+    deps: Deps,
+) -> StdResult<Binary> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(portal) => {
+            let info = portals().load(deps.storage, &portal)?;
+            Some(Bound::exclusive((info.minimum_sapience.as_num(), portal)))
+        }
+        None => None,
+    };
+    // An empty address sorts before every portal requiring the next level up
+    let end = Bound::exclusive((sapience_level.as_num() + 1, Addr::unchecked("")));
+
+    let portals: Vec<PortalInfo> = portals()
+        .idx
+        .sapience
+        .range(deps.storage, start, Some(end), Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, portal)| portal))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    to_binary(&PortalsResponse { portals })
+}
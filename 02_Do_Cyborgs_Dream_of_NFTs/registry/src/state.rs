@@ -0,0 +1,50 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, MultiIndex};
+use universe::species::SapienceScale;
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PortalInfo {
+    pub portal: Addr,
+    pub planet_name: String,
+    pub minimum_sapience: SapienceScale,
+}
+
+pub struct PortalIndexes<'a> {
+    pub planet: MultiIndex<'a, String, PortalInfo, Addr>,
+    pub sapience: MultiIndex<'a, u8, PortalInfo, Addr>,
+}
+
+impl<'a> IndexList<PortalInfo> for PortalIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<PortalInfo>> + '_> {
+        let v: Vec<&dyn Index<PortalInfo>> = vec![&self.planet, &self.sapience];
+        Box::new(v.into_iter())
+    }
+}
+
+// Registered JumpRings keyed by portal address, indexed by the planet 
+// they're located on and by the sapience level they require of travelers
+pub fn portals<'a>() -> IndexedMap<'a, &'a Addr, PortalInfo, PortalIndexes<'a>> {
+    let indexes = PortalIndexes {
+        planet: MultiIndex::new(
+            |_pk, d: &PortalInfo| d.planet_name.clone(),
+            "portals",
+            "portals__planet",
+        ),
+        sapience: MultiIndex::new(
+            |_pk, d: &PortalInfo| d.minimum_sapience.as_num(),
+            "portals",
+            "portals__sapience",
+        ),
+    };
+    IndexedMap::new("portals", indexes)
+}
@@ -2,6 +2,7 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use std::env::current_dir;
 use std::fs::create_dir_all;
 
+use universe::dna::CyborgDna;
 use universe::section31::ExecuteMsg;
 use universe::species::{SapienceResponse, SapienceScale, Sapient, Traveler};
 
@@ -16,4 +17,5 @@ fn main() {
     export_schema(&schema_for!(SapienceResponse), &out_dir);
     export_schema(&schema_for!(Sapient), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(CyborgDna), &out_dir);
 }
@@ -0,0 +1,189 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Version of the DNA string encoding produced by `CyborgDna::to_string`
+pub const DNA_VERSION: u8 = 1;
+
+/// Cyborg DNA, as generated by the potion and recorded on passports.
+///
+/// Encoded as `v<version>-<genes>-<checksum>`, where genes are lowercase hex
+/// (two characters per gene) and the checksum is a 4 character hex Fletcher-16
+/// over the version and genes, e.g. `v1-0309000402-4313`
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct CyborgDna {
+    pub version: u8,
+    pub genes: Vec<u8>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DnaError {
+    #[error("DNA must be formatted as v<version>-<genes>-<checksum>")]
+    Malformed {},
+
+    #[error("Unsupported DNA version {version}")]
+    UnsupportedVersion { version: u8 },
+
+    #[error("DNA must contain at least one gene")]
+    NoGenes {},
+
+    #[error("DNA checksum doesn't match its genes")]
+    ChecksumMismatch {},
+}
+
+impl CyborgDna {
+    pub fn new(genes: Vec<u8>) -> Self {
+        CyborgDna {
+            version: DNA_VERSION,
+            genes,
+        }
+    }
+
+    pub fn parse(dna: &str) -> Result<Self, DnaError> {
+        let parts: Vec<&str> = dna.split('-').collect();
+        if parts.len() != 3 {
+            return Err(DnaError::Malformed {});
+        }
+
+        let version = match parts[0].strip_prefix('v') {
+            Some(v) if is_decimal(v) => v.parse::<u8>().map_err(|_| DnaError::Malformed {})?,
+            _ => return Err(DnaError::Malformed {}),
+        };
+        if version != DNA_VERSION {
+            return Err(DnaError::UnsupportedVersion { version });
+        }
+
+        let genes = decode_hex(parts[1])?;
+        if genes.is_empty() {
+            return Err(DnaError::NoGenes {});
+        }
+
+        let checksum = decode_hex(parts[2])?;
+        let parsed = CyborgDna { version, genes };
+        if checksum != parsed.checksum().to_be_bytes() {
+            return Err(DnaError::ChecksumMismatch {});
+        }
+        Ok(parsed)
+    }
+
+    pub fn validate(dna: &str) -> Result<(), DnaError> {
+        CyborgDna::parse(dna).map(|_| ())
+    }
+
+    /// Fletcher-16 checksum over the encoding version and genes
+    pub fn checksum(&self) -> u16 {
+        let (mut low, mut high) = (0u16, 0u16);
+        for byte in std::iter::once(&self.version).chain(self.genes.iter()) {
+            low = (low + *byte as u16) % 255;
+            high = (high + low) % 255;
+        }
+        (high << 8) | low
+    }
+}
+
+impl fmt::Display for CyborgDna {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v{}-", self.version)?;
+        for gene in &self.genes {
+            write!(f, "{:02x}", gene)?;
+        }
+        write!(f, "-{:04x}", self.checksum())
+    }
+}
+
+impl FromStr for CyborgDna {
+    type Err = DnaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        CyborgDna::parse(s)
+    }
+}
+
+// Leading zeros would let the same version be written several ways
+fn is_decimal(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) && (s == "0" || !s.starts_with('0'))
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, DnaError> {
+    // Only the canonical (lowercase) encoding is accepted
+    let is_hex = s.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'));
+    if !is_hex {
+        return Err(DnaError::Malformed {});
+    }
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| match pair {
+            [high, low] => Ok((hex_value(*high) << 4) | hex_value(*low)),
+            _ => Err(DnaError::Malformed {}),
+        })
+        .collect()
+}
+
+fn hex_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        _ => c - b'a' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let dna = CyborgDna::new(vec![3, 9, 0, 4, 2]);
+        let encoded = dna.to_string();
+        assert_eq!(encoded, "v1-0309000402-4313");
+        assert_eq!(CyborgDna::parse(&encoded), Ok(dna.clone()));
+        assert_eq!(encoded.parse::<CyborgDna>(), Ok(dna));
+
+        let dna = CyborgDna::new(vec![255; 32]);
+        assert_eq!(CyborgDna::parse(&dna.to_string()), Ok(dna));
+    }
+
+    #[test]
+    fn malformed_dna() {
+        let encoded = CyborgDna::new(vec![3, 9, 0, 4, 2]).to_string();
+        let checksum = &encoded[encoded.len() - 4..];
+
+        assert_eq!(CyborgDna::validate(""), Err(DnaError::Malformed {}));
+        assert_eq!(CyborgDna::validate("Example DNA String"), Err(DnaError::Malformed {}));
+        assert_eq!(CyborgDna::validate("0309000402"), Err(DnaError::Malformed {}));
+        assert_eq!(
+            CyborgDna::validate(&format!("1-0309000402-{}", checksum)),
+            Err(DnaError::Malformed {})
+        );
+        assert_eq!(
+            CyborgDna::validate(&format!("v01-0309000402-{}", checksum)),
+            Err(DnaError::Malformed {})
+        );
+        assert_eq!(
+            CyborgDna::validate(&format!("v1-030900040-{}", checksum)),
+            Err(DnaError::Malformed {})
+        );
+        assert_eq!(
+            CyborgDna::validate(&format!("v1-03090004zz-{}", checksum)),
+            Err(DnaError::Malformed {})
+        );
+        assert_eq!(
+            CyborgDna::validate(&CyborgDna::new(vec![171, 205]).to_string().to_uppercase().replacen('V', "v", 1)),
+            Err(DnaError::Malformed {})
+        );
+        assert_eq!(
+            CyborgDna::validate(&format!("{}-00", encoded)),
+            Err(DnaError::Malformed {})
+        );
+        assert_eq!(CyborgDna::validate("v1--0100"), Err(DnaError::NoGenes {}));
+        assert_eq!(
+            CyborgDna::validate(&format!("v2-0309000402-{}", checksum)),
+            Err(DnaError::UnsupportedVersion { version: 2 })
+        );
+        assert_eq!(
+            CyborgDna::validate(&format!("v1-0309000403-{}", checksum)),
+            Err(DnaError::ChecksumMismatch {})
+        );
+    }
+}
@@ -1,3 +1,5 @@
+pub mod dna;
+pub mod migrate;
 pub mod section31;
 pub mod species;
 pub mod utils;
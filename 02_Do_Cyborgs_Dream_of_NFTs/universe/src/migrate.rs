@@ -0,0 +1,69 @@
+use cosmwasm_std::StdError;
+use semver::Version;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MigrateError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Cannot migrate {found} to {expected}")]
+    WrongContract { expected: String, found: String },
+
+    #[error("Cannot downgrade from version {stored} to {current}")]
+    CannotDowngrade { stored: String, current: String },
+}
+
+/// Checks that a contract stored as `stored_contract` at `stored_version`,
+/// as recorded by cw2, can be migrated to `contract_name` at `contract_version`.
+/// It has to be the same contract, at the same or an older version
+pub fn check_upgrade(
+    stored_contract: &str,
+    stored_version: &str,
+    contract_name: &str,
+    contract_version: &str,
+) -> Result<(), MigrateError> {
+    if stored_contract != contract_name {
+        return Err(MigrateError::WrongContract {
+            expected: contract_name.to_string(),
+            found: stored_contract.to_string(),
+        });
+    }
+    if parse_version(stored_version)? > parse_version(contract_version)? {
+        return Err(MigrateError::CannotDowngrade {
+            stored: stored_version.to_string(),
+            current: contract_version.to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn parse_version(version: &str) -> Result<Version, MigrateError> {
+    Version::parse(version).map_err(|e| MigrateError::Std(StdError::generic_err(e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checking_upgrades() {
+        assert_eq!(check_upgrade("portal", "0.1.0", "portal", "0.2.0"), Ok(()));
+        assert_eq!(check_upgrade("portal", "0.2.0", "portal", "0.2.0"), Ok(()));
+        assert_eq!(
+            check_upgrade("portal", "0.10.0", "portal", "0.9.0"),
+            Err(MigrateError::CannotDowngrade {
+                stored: "0.10.0".to_string(),
+                current: "0.9.0".to_string(),
+            })
+        );
+        assert_eq!(
+            check_upgrade("potion", "0.1.0", "portal", "0.2.0"),
+            Err(MigrateError::WrongContract {
+                expected: "portal".to_string(),
+                found: "potion".to_string(),
+            })
+        );
+        assert!(check_upgrade("portal", "one", "portal", "0.2.0").is_err());
+    }
+}